@@ -1,26 +1,55 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Api, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Order, Response, StdResult, Uint128,
+    from_binary, to_binary, Addr, Api, Binary, Coin, CosmosMsg, Decimal, Decimal256, Deps, DepsMut,
+    Env, MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128, Uint256,
 };
 use cw2::set_contract_version;
-use cw_utils::{one_coin, NativeBalance};
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::Bound;
+use cw_utils::{NativeBalance, PaymentError};
 
 use crate::error::ContractError;
+use crate::helpers::{cw20_denom, send_coins};
 use crate::msg::{
-    AllRoundsResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RoundResponse,
-    TreasuryBalanceResponse, UserBetResponse,
+    AcceptedCw20Token, AllRoundsResponse, AssetPriceSource, ClaimableResponse, ConfigResponse,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PriceSourceMsg, QueryMsg, ReceiveMsg, RoundBet,
+    RoundBetsResponse, RoundFilter, RoundOddsResponse, RoundOrder, RoundResponse,
+    RoundSeriesResponse, SideOdds, TreasuryBalanceResponse, UserBetResponse, UserBetsResponse,
+    UserRoundBet,
 };
+use crate::payout::{
+    balance_value, bet_payout, claimable_coins, compute_payout, fee_amount, keeper_reward,
+    payout_multiplier, round_outcome, winning_side, PayoutKind, BPS_DENOMINATOR, DEFAULT_FEE_BPS,
+    MAX_FEE_BPS, MAX_KEEPER_REWARD_BPS, MAX_SIDE_SWITCH_PENALTY_BPS,
+};
+use crate::price::{bet_denom_prices, exceeds_price_deviation, query_price};
 use crate::state::{
-    Bet, Config, Round, Side, TreasuryBalance, BET, CONFIG, ROUND, TREASURYBALANCE,
+    bets, rounds, Bet, BetDenom, Config, Cw20Token, DenomPrice, DenomPriceSource, Distribution,
+    LegacyConfig, LegacyRound, PriceSource, ReportedPrice, Round, RoundOutcome, RoundSeries,
+    RoundStatus, Side, TreasuryBalance, CONFIG, DEFAULT_CLAIM_WINDOW, DEFAULT_START_GRACE_PERIOD,
+    DEFAULT_STOP_GRACE_PERIOD, DISTRIBUTION, LEGACY_BET, LEGACY_CONFIG, LEGACY_ROUND,
+    REPORTED_PRICES, ROUND_COUNT, ROUND_SERIES, TREASURYBALANCE,
 };
-use kujira::querier::KujiraQuerier;
 use kujira::query::KujiraQuery;
-use std::str::FromStr;
+use std::collections::BTreeMap;
 
 const CONTRACT_NAME: &str = "crates.io:prediction-game";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// round timing defaults in seconds
+const DEFAULT_ROUND_DURATION: u64 = 300;
+const DEFAULT_MIN_ROUND_DURATION: u64 = 60;
+const DEFAULT_MAX_ROUND_DURATION: u64 = 86_400;
+const DEFAULT_MIN_LEAD_TIME: u64 = 300;
+
+// page sizes for queries that list rounds and bets
+const DEFAULT_QUERY_LIMIT: u32 = 10;
+const MAX_QUERY_LIMIT: u32 = 30;
+
+// page sizes for distributing a round and claiming across rounds, in bets handled per call
+const DEFAULT_PAYOUT_LIMIT: u32 = 10;
+const MAX_PAYOUT_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut<KujiraQuery>,
@@ -29,28 +58,391 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let fee_bps = msg.fee_bps.unwrap_or(DEFAULT_FEE_BPS);
+    validate_fee(fee_bps)?;
     let config = Config {
         admins: map_validate(deps.api, &msg.admins)?,
-        asset_denom: msg.asset_denom,
-        accepted_bet_denoms: msg.accepted_bet_denoms,
+        asset_denoms: msg.asset_denoms,
+        accepted_bet_denoms: validate_bet_denoms(msg.accepted_bet_denoms)?,
+        fee_bps,
+        min_round_duration: msg.min_round_duration.unwrap_or(DEFAULT_MIN_ROUND_DURATION),
+        max_round_duration: msg.max_round_duration.unwrap_or(DEFAULT_MAX_ROUND_DURATION),
+        min_lead_time: msg.min_lead_time.unwrap_or(DEFAULT_MIN_LEAD_TIME),
+        start_grace_period: msg.start_grace_period.unwrap_or(DEFAULT_START_GRACE_PERIOD),
+        stop_grace_period: msg.stop_grace_period.unwrap_or(DEFAULT_STOP_GRACE_PERIOD),
+        keeper_reward_bps: msg.keeper_reward_bps.unwrap_or_default(),
+        claim_window: msg.claim_window.unwrap_or(DEFAULT_CLAIM_WINDOW),
+        side_switch_penalty_bps: msg.side_switch_penalty_bps.unwrap_or_default(),
+        accepted_cw20_tokens: validate_cw20_tokens(
+            deps.api,
+            &msg.accepted_cw20_tokens.unwrap_or_default(),
+        )?,
+        pool_caps: msg.pool_caps.unwrap_or_default(),
+        max_side_ratio_bps: msg.max_side_ratio_bps,
+        max_price_deviation_bps: msg.max_price_deviation_bps,
+        oracle_grace_period: msg.oracle_grace_period,
+        price_sources: validate_price_sources(deps.api, &msg.price_sources.unwrap_or_default())?,
     };
+    validate_round_timings(&config)?;
+    validate_keeper_reward(config.keeper_reward_bps)?;
+    validate_side_switch_penalty(config.side_switch_penalty_bps)?;
+    validate_side_ratio(config.max_side_ratio_bps)?;
+    validate_price_deviation(config.max_price_deviation_bps)?;
     CONFIG.save(deps.storage, &config)?;
     let treasury_balance = TreasuryBalance {
         balance: NativeBalance(vec![]),
     };
     TREASURYBALANCE.save(deps.storage, &treasury_balance)?;
+    ROUND_COUNT.save(deps.storage, &0)?;
     Ok(Response::new().add_attribute("action", "instantiate"))
 }
 
+// builds the config of a contract that supported a single asset, every setting added since gets
+// its instantiate default
+fn migrate_config(legacy: LegacyConfig) -> Config {
+    Config {
+        admins: legacy.admins,
+        asset_denoms: vec![legacy.asset_denom],
+        accepted_bet_denoms: legacy
+            .accepted_bet_denoms
+            .into_iter()
+            .map(BetDenom::unlimited)
+            .collect(),
+        fee_bps: DEFAULT_FEE_BPS,
+        min_round_duration: DEFAULT_MIN_ROUND_DURATION,
+        max_round_duration: DEFAULT_MAX_ROUND_DURATION,
+        min_lead_time: DEFAULT_MIN_LEAD_TIME,
+        start_grace_period: DEFAULT_START_GRACE_PERIOD,
+        stop_grace_period: DEFAULT_STOP_GRACE_PERIOD,
+        keeper_reward_bps: 0,
+        claim_window: DEFAULT_CLAIM_WINDOW,
+        side_switch_penalty_bps: 0,
+        accepted_cw20_tokens: vec![],
+        pool_caps: vec![],
+        max_side_ratio_bps: None,
+        max_price_deviation_bps: None,
+        oracle_grace_period: None,
+        price_sources: vec![],
+    }
+}
+
+// converts a round stored before statuses were introduced. the old contract only had one asset
+// and a 15% fee, which it took whenever the price moved, so the outcome only looks at the prices
+fn migrate_round(legacy: LegacyRound, config: &Config, asset_denom: String) -> Round {
+    let status = if legacy.is_stopped {
+        RoundStatus::Settled
+    } else if legacy.is_started {
+        RoundStatus::Locked
+    } else {
+        RoundStatus::Open
+    };
+    let outcome = match (legacy.is_stopped, legacy.start_price, legacy.stop_price) {
+        (true, Some(start_price), Some(stop_price)) => {
+            Some(match winning_side(start_price, stop_price) {
+                Some(Side::Up) => RoundOutcome::Up,
+                Some(Side::Down) => RoundOutcome::Down,
+                None => RoundOutcome::Push,
+            })
+        }
+        (true, _, _) => Some(RoundOutcome::Push),
+        (false, _, _) => None,
+    };
+    Round {
+        id: 0,
+        name: String::new(),
+        created_at: legacy.created_at,
+        creator: legacy.creator,
+        asset_denom,
+        fee_bps: DEFAULT_FEE_BPS,
+        status,
+        status_updated_at: legacy
+            .stopped_at
+            .or(legacy.started_at)
+            .unwrap_or(legacy.created_at),
+        started_at: legacy.started_at,
+        stopped_at: legacy.stopped_at,
+        started_by: None,
+        stopped_by: None,
+        open_time: legacy.created_at,
+        start_time: legacy.start_time,
+        stop_time: legacy.stop_time,
+        participants_count: legacy.participants_count,
+        up_bets_count: legacy.up_bets_count,
+        down_bets_count: legacy.down_bets_count,
+        total_bet_amount: legacy.total_bet_amount,
+        total_up_bet_amount: legacy.total_up_bet_amount,
+        total_down_bet_amount: legacy.total_down_bet_amount,
+        start_price: legacy.start_price,
+        stop_price: legacy.stop_price,
+        outcome,
+        settlement_prices: vec![],
+        series: None,
+        claim_window: config.claim_window,
+        claimed_amount: NativeBalance(vec![]),
+        swept_amount: None,
+        start_grace_period: config.start_grace_period,
+        stop_grace_period: config.stop_grace_period,
+        pool_caps: vec![],
+        max_side_ratio_bps: None,
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut<KujiraQuery>,
+    env: Env,
+    _msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    // a config stored with a single asset is converted first since the rounds take their asset
+    // from it
+    if CONFIG.load(deps.storage).is_err() {
+        let legacy_config = LEGACY_CONFIG.load(deps.storage)?;
+        CONFIG.save(deps.storage, &migrate_config(legacy_config))?;
+    }
+    let config = CONFIG.load(deps.storage)?;
+    // rounds keyed by name get ids in the order they were created
+    let mut legacy_rounds = LEGACY_ROUND
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    legacy_rounds.sort_by_key(|(_, round)| round.created_at);
+    let mut round_ids: BTreeMap<String, u64> = BTreeMap::new();
+    let mut push_round_ids: Vec<u64> = vec![];
+    for (name, legacy_round) in legacy_rounds {
+        LEGACY_ROUND.remove(deps.storage, name.clone());
+        let asset_denom = config
+            .asset_denoms
+            .first()
+            .cloned()
+            .ok_or_else(|| StdError::not_found("Asset denom"))?;
+        let mut round = migrate_round(legacy_round, &config, asset_denom);
+        // the old contract priced the pool when claiming, the prices at migration are used for
+        // every claim from now on
+        if round.status == RoundStatus::Settled {
+            round.settlement_prices = bet_denom_prices(
+                deps.as_ref(),
+                &config,
+                &round.total_bet_amount,
+                env.block.time.seconds(),
+            )?;
+        }
+        let is_push = round.outcome == Some(RoundOutcome::Push);
+        let id = save_new_round(deps.storage, name.clone(), round)?;
+        if is_push {
+            push_round_ids.push(id);
+        }
+        round_ids.insert(name, id);
+    }
+    let legacy_bets = LEGACY_BET
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for ((round_name, addr), bet) in legacy_bets {
+        LEGACY_BET.remove(deps.storage, (round_name.clone(), addr.clone()));
+        let id = round_ids
+            .get(&round_name)
+            .ok_or_else(|| StdError::not_found("Round"))?;
+        let mut bet = Bet::from(bet);
+        // the old contract refunded a push on every claim without marking the bet claimed, so
+        // the bets of a push are taken as refunded to keep them from being refunded again
+        if push_round_ids.contains(id) {
+            bet.win_claimed = true;
+        }
+        bets().save(deps.storage, (*id, addr), &bet)?;
+    }
+    // the amount claimed from each round is rebuilt from the bets already claimed so that
+    // only what is really left can be swept once the claim window is over
+    let mut claimed_bets: BTreeMap<u64, Vec<Bet>> = BTreeMap::new();
+    for item in bets().range(deps.storage, None, None, Order::Ascending) {
+        let ((round_id, _), bet) = item?;
+        if bet.win_claimed {
+            claimed_bets.entry(round_id).or_default().push(bet);
+        }
+    }
+    for (round_id, claimed) in claimed_bets {
+        let mut round = rounds().load(deps.storage, round_id)?;
+        round.claimed_amount = NativeBalance::default();
+        for bet in claimed {
+            if let Some(payout) = bet_payout(&round, &bet, round.status)? {
+                for coin in payout.coins {
+                    round.claimed_amount += coin;
+                }
+            }
+        }
+        round.claimed_amount.normalize();
+        rounds().save(deps.storage, round_id, &round)?;
+    }
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("migrated_rounds", round_ids.len().to_string()))
+}
+
 pub fn map_validate(api: &dyn Api, admins: &[String]) -> StdResult<Vec<Addr>> {
     admins.iter().map(|addr| api.addr_validate(addr)).collect()
 }
 
+pub fn validate_bet_denoms(bet_denoms: Vec<BetDenom>) -> Result<Vec<BetDenom>, ContractError> {
+    for bet_denom in bet_denoms.iter() {
+        if bet_denom
+            .max_bet
+            .map_or(false, |max_bet| max_bet < bet_denom.min_bet)
+        {
+            return Err(ContractError::InvalidBetLimits {
+                denom: bet_denom.denom.clone(),
+            });
+        }
+    }
+    Ok(bet_denoms)
+}
+
+pub fn validate_cw20_tokens(
+    api: &dyn Api,
+    tokens: &[AcceptedCw20Token],
+) -> Result<Vec<Cw20Token>, ContractError> {
+    tokens
+        .iter()
+        .map(|token| {
+            if token
+                .max_bet
+                .map_or(false, |max_bet| max_bet < token.min_bet)
+            {
+                return Err(ContractError::InvalidBetLimits {
+                    denom: cw20_denom(&token.address),
+                });
+            }
+            Ok(Cw20Token {
+                address: api.addr_validate(&token.address)?,
+                oracle_denom: token.oracle_denom.clone(),
+                min_bet: token.min_bet,
+                max_bet: token.max_bet,
+            })
+        })
+        .collect()
+}
+
+pub fn validate_price_sources(
+    api: &dyn Api,
+    price_sources: &[AssetPriceSource],
+) -> StdResult<Vec<DenomPriceSource>> {
+    price_sources
+        .iter()
+        .map(|price_source| {
+            let source = match &price_source.source {
+                PriceSourceMsg::Oracle {} => PriceSource::Oracle {},
+                PriceSourceMsg::Fin { pair } => PriceSource::Fin {
+                    pair: api.addr_validate(pair)?,
+                },
+                PriceSourceMsg::Reporter { reporter, max_age } => PriceSource::Reporter {
+                    reporter: api.addr_validate(reporter)?,
+                    max_age: *max_age,
+                },
+            };
+            Ok(DenomPriceSource {
+                denom: price_source.denom.clone(),
+                source,
+            })
+        })
+        .collect()
+}
+
 pub fn sender_is_admin(config: &Config, sender: &str) -> StdResult<bool> {
-    let can = config.is_admin(&sender);
+    let can = config.is_admin(sender);
     Ok(can)
 }
 
+pub fn validate_fee(fee_bps: u16) -> Result<(), ContractError> {
+    if fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::FeeTooHigh {
+            max_fee_bps: MAX_FEE_BPS,
+        });
+    }
+    Ok(())
+}
+
+pub fn validate_side_switch_penalty(side_switch_penalty_bps: u16) -> Result<(), ContractError> {
+    if side_switch_penalty_bps > MAX_SIDE_SWITCH_PENALTY_BPS {
+        return Err(ContractError::SideSwitchPenaltyTooHigh {
+            max_side_switch_penalty_bps: MAX_SIDE_SWITCH_PENALTY_BPS,
+        });
+    }
+    Ok(())
+}
+
+pub fn validate_side_ratio(max_side_ratio_bps: Option<u32>) -> Result<(), ContractError> {
+    if max_side_ratio_bps.map_or(false, |ratio| ratio < MIN_SIDE_RATIO_BPS) {
+        return Err(ContractError::SideRatioTooLow {
+            min_side_ratio_bps: MIN_SIDE_RATIO_BPS,
+        });
+    }
+    Ok(())
+}
+
+pub fn validate_price_deviation(max_price_deviation_bps: Option<u32>) -> Result<(), ContractError> {
+    if max_price_deviation_bps == Some(0) {
+        return Err(ContractError::InvalidPriceDeviation {});
+    }
+    Ok(())
+}
+
+pub fn validate_keeper_reward(keeper_reward_bps: u16) -> Result<(), ContractError> {
+    if keeper_reward_bps > MAX_KEEPER_REWARD_BPS {
+        return Err(ContractError::KeeperRewardTooHigh {
+            max_keeper_reward_bps: MAX_KEEPER_REWARD_BPS,
+        });
+    }
+    Ok(())
+}
+
+pub fn validate_round_duration(config: &Config, duration: u64) -> Result<(), ContractError> {
+    if duration < config.min_round_duration || duration > config.max_round_duration {
+        return Err(ContractError::InvalidRoundDuration {
+            min: config.min_round_duration,
+            max: config.max_round_duration,
+        });
+    }
+    Ok(())
+}
+
+pub fn validate_round_timings(config: &Config) -> Result<(), ContractError> {
+    if config.min_round_duration == 0 {
+        return Err(ContractError::InvalidRoundTimings {
+            message: String::from("min_round_duration should be greater than 0"),
+        });
+    }
+    if config.min_round_duration > config.max_round_duration {
+        return Err(ContractError::InvalidRoundTimings {
+            message: String::from("min_round_duration should not exceed max_round_duration"),
+        });
+    }
+    Ok(())
+}
+
+// returns the status of a round at the given time, a scheduled round is reported as open once
+// its open time is reached and a round that missed the window to be started or stopped is
+// reported as cancelled even before anyone cancels it
+pub fn round_status(round: &Round, current_time: u64) -> RoundStatus {
+    let status = match round.status {
+        RoundStatus::Scheduled if current_time >= round.open_time => RoundStatus::Open,
+        status => status,
+    };
+    let is_stale = match status {
+        RoundStatus::Open => {
+            let start_deadline = round
+                .stop_time
+                .min(round.start_time.saturating_add(round.start_grace_period));
+            current_time > start_deadline
+        }
+        RoundStatus::Locked => {
+            current_time > round.stop_time.saturating_add(round.stop_grace_period)
+        }
+        RoundStatus::Scheduled | RoundStatus::Settled | RoundStatus::Cancelled => false,
+    };
+    if is_stale {
+        RoundStatus::Cancelled
+    } else {
+        status
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut<KujiraQuery>,
@@ -60,22 +452,110 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::UpdateAdmins { admins } => execute_update_admins(deps, info, admins),
-        ExecuteMsg::UpdateAssetDenom { asset_denom } => {
-            execute_update_asset_denom(deps, info, asset_denom)
+        ExecuteMsg::UpdateAssetDenoms { asset_denoms } => {
+            execute_update_asset_denoms(deps, info, asset_denoms)
+        }
+        ExecuteMsg::UpdatePriceSources { price_sources } => {
+            execute_update_price_sources(deps, info, price_sources)
         }
         ExecuteMsg::UpdateAcceptedBetDenoms {
             accepted_bet_denoms,
         } => execute_update_accepted_bet_denoms(deps, info, accepted_bet_denoms),
-        ExecuteMsg::CreateRound { start_time, name } => {
-            execute_create_round(deps, info, env, start_time, name)
+        ExecuteMsg::UpdateAcceptedCw20Tokens {
+            accepted_cw20_tokens,
+        } => execute_update_accepted_cw20_tokens(deps, info, accepted_cw20_tokens),
+        ExecuteMsg::UpdateFee { fee_bps } => execute_update_fee(deps, info, fee_bps),
+        ExecuteMsg::UpdateKeeperReward { keeper_reward_bps } => {
+            execute_update_keeper_reward(deps, info, keeper_reward_bps)
+        }
+        ExecuteMsg::UpdateClaimWindow { claim_window } => {
+            execute_update_claim_window(deps, info, claim_window)
         }
+        ExecuteMsg::UpdateSideSwitchPenalty {
+            side_switch_penalty_bps,
+        } => execute_update_side_switch_penalty(deps, info, side_switch_penalty_bps),
+        ExecuteMsg::UpdateRoundLimits {
+            pool_caps,
+            max_side_ratio_bps,
+        } => execute_update_round_limits(deps, info, pool_caps, max_side_ratio_bps),
+        ExecuteMsg::UpdateOracleGuards {
+            max_price_deviation_bps,
+            oracle_grace_period,
+        } => execute_update_oracle_guards(deps, info, max_price_deviation_bps, oracle_grace_period),
+        ExecuteMsg::UpdateRoundTimings {
+            min_round_duration,
+            max_round_duration,
+            min_lead_time,
+            start_grace_period,
+            stop_grace_period,
+        } => execute_update_round_timings(
+            deps,
+            info,
+            min_round_duration,
+            max_round_duration,
+            min_lead_time,
+            start_grace_period,
+            stop_grace_period,
+        ),
+        ExecuteMsg::CreateRound {
+            start_time,
+            name,
+            asset_denom,
+            duration,
+            open_time,
+        } => execute_create_round(
+            deps,
+            info,
+            env,
+            start_time,
+            name,
+            asset_denom,
+            duration,
+            open_time,
+        ),
+        ExecuteMsg::CreateRoundSeries {
+            name,
+            asset_denom,
+            start_time,
+            interval,
+            duration,
+            bet_denoms,
+        } => execute_create_round_series(
+            deps,
+            info,
+            env,
+            name,
+            asset_denom,
+            start_time,
+            interval,
+            duration,
+            bet_denoms,
+        ),
+        ExecuteMsg::EndRoundSeries { name } => execute_end_round_series(deps, info, name),
         ExecuteMsg::PlaceBet { side, round_name } => {
             execute_place_bet(deps, info, env, side, round_name)
         }
+        ExecuteMsg::Receive(msg) => execute_receive(deps, info, env, msg),
+        ExecuteMsg::SwitchBetSide { side, round_name } => {
+            execute_switch_bet_side(deps, info, env, side, round_name)
+        }
         ExecuteMsg::WithdrawBet { round_name } => execute_withdraw_bet(deps, info, env, round_name),
         ExecuteMsg::StartRound { name } => execute_start_round(deps, info, env, name),
         ExecuteMsg::StopRound { name } => execute_stop_round(deps, info, env, name),
         ExecuteMsg::ClaimWin { round_name } => execute_claim_win(deps, info, env, round_name),
+        ExecuteMsg::CancelRound { name } => execute_cancel_round(deps, env, name),
+        ExecuteMsg::ClaimRefund { round_name } => execute_claim_refund(deps, info, env, round_name),
+        ExecuteMsg::ClaimWins { round_names } => execute_claim_wins(deps, info, env, round_names),
+        ExecuteMsg::ClaimAll { start_after, limit } => {
+            execute_claim_all(deps, info, env, start_after, limit)
+        }
+        ExecuteMsg::DistributeRound { round_name, limit } => {
+            execute_distribute_round(deps, env, round_name, limit)
+        }
+        ExecuteMsg::SweepExpired { round_name } => execute_sweep_expired(deps, env, round_name),
+        ExecuteMsg::ReportPrice { denom, price } => {
+            execute_report_price(deps, info, env, denom, price)
+        }
         ExecuteMsg::WithdrawFromPool {
             to_address,
             denom,
@@ -91,7 +571,7 @@ pub fn execute_update_admins(
     admins: Vec<String>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    let is_admin = sender_is_admin(&config, &info.sender.as_str())?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
     if !is_admin {
         return Err(ContractError::Unauthorized {});
     }
@@ -101,226 +581,889 @@ pub fn execute_update_admins(
     Ok(Response::new().add_attribute("action", "update admins"))
 }
 
-// updates the asset which users can bet on in the contract
-pub fn execute_update_asset_denom(
+// updates the list of assets which rounds can be created for, existing rounds keep their asset
+pub fn execute_update_asset_denoms(
     deps: DepsMut<KujiraQuery>,
     info: MessageInfo,
-    asset_denom: String,
+    asset_denoms: Vec<String>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    let is_admin = sender_is_admin(&config, &info.sender.as_str())?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
     if !is_admin {
         return Err(ContractError::Unauthorized {});
     }
-    config.asset_denom = asset_denom;
+    config.asset_denoms = asset_denoms;
     CONFIG.save(deps.storage, &config)?;
-    Ok(Response::new().add_attribute("action", "update asset denom"))
+    Ok(Response::new().add_attribute("action", "update asset denoms"))
 }
 
 // updates the list of denoms accepted when betting
 pub fn execute_update_accepted_bet_denoms(
     deps: DepsMut<KujiraQuery>,
     info: MessageInfo,
-    accepted_bet_denoms: Vec<String>,
+    accepted_bet_denoms: Vec<BetDenom>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    let is_admin = sender_is_admin(&config, &info.sender.as_str())?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
     if !is_admin {
         return Err(ContractError::Unauthorized {});
     }
-    config.accepted_bet_denoms = accepted_bet_denoms;
+    config.accepted_bet_denoms = validate_bet_denoms(accepted_bet_denoms)?;
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new().add_attribute("action", "update accepted bet denoms"))
 }
 
+// updates the list of cw20 tokens accepted when betting
+pub fn execute_update_accepted_cw20_tokens(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    accepted_cw20_tokens: Vec<AcceptedCw20Token>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.accepted_cw20_tokens = validate_cw20_tokens(deps.api, &accepted_cw20_tokens)?;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update accepted cw20 tokens"))
+}
+
+// updates the fee taken from rounds created from now on, existing rounds keep their fee
+pub fn execute_update_fee(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    fee_bps: u16,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_fee(fee_bps)?;
+    config.fee_bps = fee_bps;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update fee"))
+}
+
+// updates the share of round fees paid to whoever starts or stops a round
+pub fn execute_update_keeper_reward(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    keeper_reward_bps: u16,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_keeper_reward(keeper_reward_bps)?;
+    config.keeper_reward_bps = keeper_reward_bps;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update keeper reward"))
+}
+
+// updates how long bettors have to claim from rounds created from now on, existing rounds keep
+// their claim window
+pub fn execute_update_claim_window(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    claim_window: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.claim_window = claim_window;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update claim window"))
+}
+
+// updates the share of a bet taken when a user switches it to the other side
+pub fn execute_update_side_switch_penalty(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    side_switch_penalty_bps: u16,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_side_switch_penalty(side_switch_penalty_bps)?;
+    config.side_switch_penalty_bps = side_switch_penalty_bps;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update side switch penalty"))
+}
+
+// updates the pool caps and side ratio limit of rounds created from now on, existing rounds keep
+// their limits
+pub fn execute_update_round_limits(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    pool_caps: Vec<Coin>,
+    max_side_ratio_bps: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_side_ratio(max_side_ratio_bps)?;
+    config.pool_caps = pool_caps;
+    config.max_side_ratio_bps = max_side_ratio_bps;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update round limits"))
+}
+
+// updates where the prices of denoms are read from, this applies to every round from now on
+pub fn execute_update_price_sources(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    price_sources: Vec<AssetPriceSource>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.price_sources = validate_price_sources(deps.api, &price_sources)?;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update price sources"))
+}
+
+// updates how far the stop price can move from the start price and how long a round waits for a
+// good oracle price before it is cancelled, this applies to every round from now on
+pub fn execute_update_oracle_guards(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    max_price_deviation_bps: Option<u32>,
+    oracle_grace_period: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_price_deviation(max_price_deviation_bps)?;
+    config.max_price_deviation_bps = max_price_deviation_bps;
+    config.oracle_grace_period = oracle_grace_period;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update oracle guards"))
+}
+
+// updates the bounds for the duration of new rounds, how far ahead they have to be created and
+// how long they can wait to be started and stopped before they can be cancelled. rounds that
+// were already created keep their grace periods
+pub fn execute_update_round_timings(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    min_round_duration: u64,
+    max_round_duration: u64,
+    min_lead_time: u64,
+    start_grace_period: u64,
+    stop_grace_period: u64,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.min_round_duration = min_round_duration;
+    config.max_round_duration = max_round_duration;
+    config.min_lead_time = min_lead_time;
+    config.start_grace_period = start_grace_period;
+    config.stop_grace_period = stop_grace_period;
+    validate_round_timings(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "update round timings"))
+}
+
 // creates a round that users can bet on, start_time is the time when the round should start and
 // name is the name of the round, this can also be a unique id
+// asset_denom is the asset whose price users will bet on, it has to be one of the config assets
+// duration is how long the round runs for in seconds, it defaults to 5 mins
+// open_time is when the round starts accepting bets, until then it is scheduled
+#[allow(clippy::too_many_arguments)]
 pub fn execute_create_round(
     deps: DepsMut<KujiraQuery>,
     info: MessageInfo,
     env: Env,
     start_time: u64,
     name: String,
+    asset_denom: String,
+    duration: Option<u64>,
+    open_time: Option<u64>,
 ) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.asset_denoms.contains(&asset_denom) {
+        return Err(ContractError::AssetNotSupported {});
+    }
     let current_time = env.block.time.seconds();
-    let in_five_mins = current_time + 300;
-    if start_time < in_five_mins {
+    let open_time = open_time.map_or(current_time, |open_time| open_time.max(current_time));
+    if start_time < open_time.saturating_add(config.min_lead_time) {
         return Err(ContractError::InvalidStartTime {
-            message: String::from(
-                "start_time should be at least 5 mins away from round creation time",
+            message: format!(
+                "start_time should be at least {} seconds away from the round open time",
+                config.min_lead_time
             ),
         });
     }
-    let stop_time = start_time + 300;
-    let existing_round = ROUND.may_load(deps.storage, name.clone())?;
-    match existing_round {
+    let duration = duration.unwrap_or(DEFAULT_ROUND_DURATION);
+    validate_round_duration(&config, duration)?;
+    let stop_time = round_stop_time(start_time, duration)?;
+    let existing_round = may_load_round(deps.storage, &name)?;
+    let round_id = match existing_round {
         Some(_round) => return Err(ContractError::RoundAlreadyExists {}),
         None => {
-            let new_round = Round {
-                created_at: current_time,
-                creator: info.sender,
+            let mut new_round = new_open_round(
+                info.sender,
+                asset_denom,
+                &config,
                 start_time,
                 stop_time,
-                participants_count: 0,
-                up_bets_count: 0,
-                down_bets_count: 0,
-                total_bet_amount: NativeBalance(vec![]),
-                total_up_bet_amount: NativeBalance(vec![]),
-                total_down_bet_amount: NativeBalance(vec![]),
-                is_started: false,
-                started_at: None,
-                is_stopped: false,
-                stopped_at: None,
-                start_price: None,
-                stop_price: None,
-            };
-            ROUND.save(deps.storage, name, &new_round)?;
+                current_time,
+                None,
+            );
+            if open_time > current_time {
+                new_round.status = RoundStatus::Scheduled;
+                new_round.open_time = open_time;
+            }
+            save_new_round(deps.storage, name, new_round)?
         }
+    };
+    Ok(Response::new()
+        .add_attribute("action", "Create round")
+        .add_attribute("round_id", round_id.to_string()))
+}
+
+// returns the stop time of a round that runs for duration seconds from start_time, a stop time
+// that does not fit in a timestamp is rejected
+fn round_stop_time(start_time: u64, duration: u64) -> Result<u64, ContractError> {
+    start_time
+        .checked_add(duration)
+        .ok_or_else(|| ContractError::InvalidStartTime {
+            message: String::from("start_time plus the round duration is out of range"),
+        })
+}
+
+// loads a round by its unique name
+pub fn load_round(storage: &dyn Storage, name: &str) -> StdResult<Round> {
+    may_load_round(storage, name)?.ok_or_else(|| StdError::not_found("Round"))
+}
+
+pub fn may_load_round(storage: &dyn Storage, name: &str) -> StdResult<Option<Round>> {
+    let round = rounds().idx.name.item(storage, name.to_string())?;
+    Ok(round.map(|(_, round)| round))
+}
+
+// saves a new round under the next round id and returns the id
+fn save_new_round(storage: &mut dyn Storage, name: String, round: Round) -> StdResult<u64> {
+    let id = ROUND_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    let round = Round { id, name, ..round };
+    rounds().save(storage, id, &round)?;
+    ROUND_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+// builds a round that accepts bets until its start time, the fee, claim window and round limits
+// are taken from the current config
+fn new_open_round(
+    creator: Addr,
+    asset_denom: String,
+    config: &Config,
+    start_time: u64,
+    stop_time: u64,
+    current_time: u64,
+    series: Option<String>,
+) -> Round {
+    Round {
+        id: 0,
+        name: String::new(),
+        created_at: current_time,
+        creator,
+        asset_denom,
+        fee_bps: config.fee_bps,
+        open_time: current_time,
+        start_time,
+        stop_time,
+        participants_count: 0,
+        up_bets_count: 0,
+        down_bets_count: 0,
+        total_bet_amount: NativeBalance(vec![]),
+        total_up_bet_amount: NativeBalance(vec![]),
+        total_down_bet_amount: NativeBalance(vec![]),
+        status: RoundStatus::Open,
+        status_updated_at: current_time,
+        started_at: None,
+        stopped_at: None,
+        started_by: None,
+        stopped_by: None,
+        start_price: None,
+        stop_price: None,
+        outcome: None,
+        settlement_prices: vec![],
+        series,
+        claim_window: config.claim_window,
+        claimed_amount: NativeBalance(vec![]),
+        swept_amount: None,
+        start_grace_period: config.start_grace_period,
+        stop_grace_period: config.stop_grace_period,
+        pool_caps: config.pool_caps.clone(),
+        max_side_ratio_bps: config.max_side_ratio_bps,
     }
-    Ok(Response::new().add_attribute("action", "Create round"))
 }
 
-// enables an admin to start a given round so that it can be initialised with a starting price
-// name is the unique name of the round to be started
-pub fn execute_start_round(
+// creates a series of recurring rounds for an asset and opens its first round at start_time.
+// interval is the time between the start of consecutive rounds, it has to leave room for the
+// round duration and the minimum betting window of the next round.
+// bet_denoms are the denoms accepted in rounds of the series, they have to be config bet denoms
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create_round_series(
     deps: DepsMut<KujiraQuery>,
     info: MessageInfo,
     env: Env,
     name: String,
+    asset_denom: String,
+    start_time: u64,
+    interval: u64,
+    duration: Option<u64>,
+    bet_denoms: Vec<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let is_admin = sender_is_admin(&config, &info.sender.as_str())?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
     if !is_admin {
         return Err(ContractError::Unauthorized {});
     }
-    let round = ROUND.load(deps.storage, name.clone())?;
-    if round.is_started {
-        return Err(ContractError::RoundAlreadyStarted {});
+    if ROUND_SERIES.has(deps.storage, name.clone()) {
+        return Err(ContractError::SeriesAlreadyExists {});
+    }
+    if !config.asset_denoms.contains(&asset_denom) {
+        return Err(ContractError::AssetNotSupported {});
+    }
+    if bet_denoms.is_empty()
+        || bet_denoms
+            .iter()
+            .any(|denom| !config.accepts_bet_denom(denom))
+    {
+        return Err(ContractError::DenomNotSupported {});
     }
     let current_time = env.block.time.seconds();
-    if current_time > round.stop_time {
-        return Err(ContractError::RoundStopTimePassed {});
+    if start_time < current_time.saturating_add(config.min_lead_time) {
+        return Err(ContractError::InvalidStartTime {
+            message: format!(
+                "start_time should be at least {} seconds away from round creation time",
+                config.min_lead_time
+            ),
+        });
     }
-    let q = KujiraQuerier::new(&deps.querier);
-    let res = q.query_exchange_rate(config.asset_denom)?;
-    let price = res.rate;
-    let mut started_round = round;
-    started_round.is_started = true;
-    started_round.started_at = Some(current_time);
-    started_round.start_price = Some(price);
-    ROUND.save(deps.storage, name, &started_round)?;
-    Ok(Response::new().add_attribute("action", "Start round"))
+    let duration = duration.unwrap_or(DEFAULT_ROUND_DURATION);
+    validate_round_duration(&config, duration)?;
+    round_stop_time(start_time, duration)?;
+    if interval < duration.saturating_add(config.min_lead_time) {
+        return Err(ContractError::InvalidRoundTimings {
+            message: format!(
+                "interval should be at least the round duration plus {} seconds",
+                config.min_lead_time
+            ),
+        });
+    }
+    let mut series = RoundSeries {
+        creator: info.sender,
+        asset_denom,
+        interval,
+        duration,
+        bet_denoms,
+        latest_sequence: 0,
+        latest_round: String::new(),
+        active: true,
+    };
+    let round_name = open_series_round(
+        deps.storage,
+        &config,
+        &name,
+        &mut series,
+        start_time,
+        current_time,
+    )?;
+    ROUND_SERIES.save(deps.storage, name, &series)?;
+    Ok(Response::new()
+        .add_attribute("action", "Create round series")
+        .add_attribute("round_name", round_name))
 }
 
-// enables a user to place a bet on a round
-// side is the side enum variant representing the side that the user is betting on
-pub fn execute_place_bet(
+// stops a series from opening new rounds, rounds that were already created are not affected
+pub fn execute_end_round_series(
     deps: DepsMut<KujiraQuery>,
     info: MessageInfo,
-    env: Env,
-    side: Side,
-    round_name: String,
+    name: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut series = ROUND_SERIES.load(deps.storage, name.clone())?;
+    series.active = false;
+    ROUND_SERIES.save(deps.storage, name, &series)?;
+    Ok(Response::new().add_attribute("action", "End round series"))
+}
 
-    let coin = one_coin(&info)?;
-
-    let denom_accepted = config.accepted_bet_denoms.contains(&coin.denom);
+// opens the next round of a series at start_time and records it as the latest round.
+// sequence numbers whose name is already taken by another round are skipped
+fn open_series_round(
+    storage: &mut dyn Storage,
+    config: &Config,
+    series_name: &str,
+    series: &mut RoundSeries,
+    start_time: u64,
+    current_time: u64,
+) -> StdResult<String> {
+    let mut sequence = series.latest_sequence + 1;
+    while may_load_round(storage, &RoundSeries::round_name(series_name, sequence))?.is_some() {
+        sequence += 1;
+    }
+    let round_name = RoundSeries::round_name(series_name, sequence);
+    let round = new_open_round(
+        series.creator.clone(),
+        series.asset_denom.clone(),
+        config,
+        start_time,
+        start_time.saturating_add(series.duration),
+        current_time,
+        Some(series_name.to_string()),
+    );
+    save_new_round(storage, round_name.clone(), round)?;
+    series.latest_sequence = sequence;
+    series.latest_round = round_name.clone();
+    Ok(round_name)
+}
 
-    if !denom_accepted {
-        return Err(ContractError::DenomNotSupported {});
+// opens the round that follows a stopped or cancelled round of a series. this only happens for
+// the latest round of an active series whose asset is still supported, so every round is
+// followed by at most one round
+fn open_next_series_round(
+    storage: &mut dyn Storage,
+    config: &Config,
+    round_name: &str,
+    round: &Round,
+    current_time: u64,
+) -> StdResult<Option<String>> {
+    let series_name = match &round.series {
+        Some(series_name) => series_name,
+        None => return Ok(None),
+    };
+    let mut series = ROUND_SERIES.load(storage, series_name.clone())?;
+    if !series.active
+        || series.latest_round != round_name
+        || !config.asset_denoms.contains(&series.asset_denom)
+    {
+        return Ok(None);
     }
+    // keep the series schedule unless the round was stopped late, new rounds always get at
+    // least the minimum betting window
+    let start_time = round
+        .start_time
+        .saturating_add(series.interval)
+        .max(current_time.saturating_add(config.min_lead_time));
+    let next_round_name = open_series_round(
+        storage,
+        config,
+        series_name,
+        &mut series,
+        start_time,
+        current_time,
+    )?;
+    ROUND_SERIES.save(storage, series_name.clone(), &series)?;
+    Ok(Some(next_round_name))
+}
 
-    let round = ROUND.load(deps.storage, round_name.clone())?;
+// starts a given round so that it can be initialised with a starting price
+// anyone can start a round once its start time is reached, admins can also start it early.
+// the caller is recorded as a keeper and gets a share of the round fees when it is stopped
+// name is the unique name of the round to be started
+pub fn execute_start_round(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    env: Env,
+    name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    let round = load_round(deps.storage, &name)?;
+    match round.status {
+        RoundStatus::Locked | RoundStatus::Settled => {
+            return Err(ContractError::RoundAlreadyStarted {})
+        }
+        RoundStatus::Cancelled => return Err(ContractError::RoundCancelled {}),
+        RoundStatus::Scheduled | RoundStatus::Open => {}
+    }
     let current_time = env.block.time.seconds();
-    if round.start_time < current_time && round.is_started {
-        return Err(ContractError::RoundAlreadyStarted {});
+    if round_status(&round, current_time) == RoundStatus::Scheduled {
+        return Err(ContractError::RoundNotOpen {});
     }
-    let sent_amount = coin.amount.u128();
-    let existing_bet = BET.may_load(deps.storage, (round_name.clone(), info.sender.clone()))?;
-    match existing_bet {
-        Some(_bet) => return Err(ContractError::BetAlreadyPlaced {}),
-        None => {
-            let new_bet = Bet {
-                side: side.clone(),
-                amount: sent_amount,
-                denom: coin.denom.clone(),
-                win_claimed: false,
-                placed_at: current_time,
-            };
-            BET.save(
-                deps.storage,
-                (round_name.clone(), info.sender.clone()),
-                &new_bet,
-            )?;
+    if current_time < round.start_time && !is_admin {
+        return Err(ContractError::RoundNotReadyToStart {});
+    }
+    if current_time > round.stop_time {
+        return Err(ContractError::RoundStopTimePassed {});
+    }
+    if round_status(&round, current_time) == RoundStatus::Cancelled {
+        return Err(ContractError::RoundStartWindowPassed {});
+    }
+    let price = match asset_price(deps.as_ref(), &config, &round.asset_denom, current_time) {
+        Ok(price) => price,
+        Err(err) if oracle_grace_passed(&config, round.start_time, current_time) => {
+            return cancel_for_bad_price(deps.storage, &config, &name, round, current_time, err)
+        }
+        Err(err) => return Err(err),
+    };
+    let mut started_round = round;
+    if started_round.status == RoundStatus::Scheduled {
+        started_round.transition(RoundStatus::Open, started_round.open_time)?;
+    }
+    started_round.transition(RoundStatus::Locked, current_time)?;
+    started_round.started_by = Some(info.sender);
+    started_round.start_price = Some(price);
+    rounds().save(deps.storage, started_round.id, &started_round)?;
+    Ok(Response::new().add_attribute("action", "Start round"))
+}
+
+// enables a user to place a bet on a round
+// side is the side enum variant representing the side that the user is betting on
+pub fn execute_place_bet(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    env: Env,
+    side: Side,
+    round_name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // a bet can be placed with several denoms at once
+    let mut stake = NativeBalance(info.funds.clone());
+    stake.normalize();
+    if stake.is_empty() {
+        return Err(PaymentError::NoFunds {}.into());
+    }
+    let denoms_accepted = stake
+        .0
+        .iter()
+        .all(|coin| config.bet_denom(&coin.denom).is_some());
+
+    if !denoms_accepted {
+        return Err(ContractError::DenomNotSupported {});
+    }
+
+    place_bet(deps, env, &config, info.sender, stake, side, round_name)
+}
+
+// enables a user to place a bet on a round with cw20 tokens, the tokens are sent to the
+// contract with a PlaceBet hook
+pub fn execute_receive(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    env: Env,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    // the sender is the token contract, only accepted tokens can be bet with
+    let denom = cw20_denom(&info.sender);
+    if config.cw20_token(&denom).is_none() {
+        return Err(ContractError::DenomNotSupported {});
+    }
+    if wrapper.amount.is_zero() {
+        return Err(PaymentError::NoFunds {}.into());
+    }
+    let bettor = deps.api.addr_validate(&wrapper.sender)?;
+    let stake = NativeBalance(vec![Coin {
+        denom,
+        amount: wrapper.amount,
+    }]);
+    match from_binary(&wrapper.msg)? {
+        ReceiveMsg::PlaceBet { side, round_name } => {
+            place_bet(deps, env, &config, bettor, stake, side, round_name)
+        }
+    }
+}
+
+// records a stake on a side of a round, the stake denoms have already been checked against
+// the config
+fn place_bet(
+    deps: DepsMut<KujiraQuery>,
+    env: Env,
+    config: &Config,
+    bettor: Addr,
+    stake: NativeBalance,
+    side: Side,
+    round_name: String,
+) -> Result<Response, ContractError> {
+    let mut round = load_round(deps.storage, &round_name)?;
+    if let Some(series_name) = &round.series {
+        let series = ROUND_SERIES.load(deps.storage, series_name.clone())?;
+        if !stake
+            .0
+            .iter()
+            .all(|coin| series.bet_denoms.contains(&coin.denom))
+        {
+            return Err(ContractError::DenomNotSupported {});
+        }
+    }
+    let current_time = env.block.time.seconds();
+    match round_status(&round, current_time) {
+        RoundStatus::Locked | RoundStatus::Settled => {
+            return Err(ContractError::RoundAlreadyStarted {})
+        }
+        RoundStatus::Cancelled => return Err(ContractError::RoundCancelled {}),
+        RoundStatus::Scheduled => return Err(ContractError::RoundNotOpen {}),
+        RoundStatus::Open => {}
+    }
+    // the first bet after the open time moves a scheduled round to open
+    if round.status == RoundStatus::Scheduled {
+        round.transition(RoundStatus::Open, round.open_time)?;
+    }
+    let existing_bet = bets().may_load(deps.storage, (round.id, bettor.clone()))?;
+    // the limits apply to the total bet in each denom, top ups included
+    for coin in stake.0.iter() {
+        if let Some((min_bet, max_bet)) = config.bet_limits(&coin.denom) {
+            let already_bet = existing_bet
+                .as_ref()
+                .and_then(|bet| bet.amount.0.iter().find(|c| c.denom == coin.denom))
+                .map_or(Uint128::zero(), |c| c.amount);
+            let total = already_bet + coin.amount;
+            if total < min_bet {
+                return Err(ContractError::BetTooSmall {
+                    denom: coin.denom.clone(),
+                    min_bet,
+                });
+            }
+            if let Some(max_bet) = max_bet.filter(|max_bet| total > *max_bet) {
+                return Err(ContractError::BetTooLarge {
+                    denom: coin.denom.clone(),
+                    max_bet,
+                });
+            }
+        }
+    }
+    match existing_bet {
+        // a bet can be topped up on the same side with any accepted denom until the round starts
+        Some(mut bet) => {
+            if bet.side != side {
+                return Err(ContractError::BetAlreadyPlaced {});
+            }
+            let mut updated_round = round;
+            match side {
+                Side::Up => updated_round.total_up_bet_amount += stake.clone(),
+                Side::Down => updated_round.total_down_bet_amount += stake.clone(),
+            }
+            updated_round.total_bet_amount += stake.clone();
+            check_round_limits(deps.as_ref(), config, &updated_round, &side, current_time)?;
+            bet.amount += stake;
+            bet.amount.normalize();
+            bets().save(deps.storage, (updated_round.id, bettor.clone()), &bet)?;
+            rounds().save(deps.storage, updated_round.id, &updated_round)?;
+            return Ok(Response::new().add_attribute("action", "increase bet"));
+        }
+        None => {
             let mut updated_round = round.clone();
             match side {
                 Side::Up => {
                     updated_round.up_bets_count += 1;
-                    updated_round.total_up_bet_amount += coin.clone();
+                    updated_round.total_up_bet_amount += stake.clone();
                 }
                 Side::Down => {
                     updated_round.down_bets_count += 1;
-                    updated_round.total_down_bet_amount += coin.clone();
+                    updated_round.total_down_bet_amount += stake.clone();
                 }
             }
 
-            updated_round.total_bet_amount += coin;
+            updated_round.total_bet_amount += stake.clone();
             updated_round.participants_count += 1;
-            ROUND.save(deps.storage, round_name, &updated_round)?;
+            check_round_limits(deps.as_ref(), config, &updated_round, &side, current_time)?;
+            let new_bet = Bet {
+                side,
+                amount: stake,
+                win_claimed: false,
+                placed_at: current_time,
+                lost: false,
+            };
+            bets().save(deps.storage, (round.id, bettor.clone()), &new_bet)?;
+            rounds().save(deps.storage, updated_round.id, &updated_round)?;
         }
     }
     Ok(Response::new().add_attribute("action", "place bet"))
 }
 
+// lowest side ratio admins are allowed to set, both sides worth the same
+const MIN_SIDE_RATIO_BPS: u32 = 10_000;
+
+// returns true if a side is worth more than max_side_ratio_bps of the opposing side, a side
+// whose opposing side has no bets yet is never over the limit
+fn exceeds_side_ratio(
+    side_value: Uint256,
+    opposing_value: Uint256,
+    max_side_ratio_bps: u32,
+) -> bool {
+    !opposing_value.is_zero()
+        && side_value.full_mul(Uint256::from(BPS_DENOMINATOR))
+            > opposing_value.full_mul(Uint256::from(max_side_ratio_bps))
+}
+
+// checks that a round about to be saved with more bet on a side stays within its pool caps and
+// its side ratio limit, the oracle is only queried when both sides have bets
+fn check_round_limits(
+    deps: Deps<KujiraQuery>,
+    config: &Config,
+    round: &Round,
+    side: &Side,
+    current_time: u64,
+) -> Result<(), ContractError> {
+    for coin in round.total_bet_amount.0.iter() {
+        if let Some(cap) = round.pool_cap(&coin.denom).filter(|cap| coin.amount > *cap) {
+            return Err(ContractError::PoolCapReached {
+                denom: coin.denom.clone(),
+                cap,
+            });
+        }
+    }
+    let max_side_ratio_bps = match round.max_side_ratio_bps {
+        Some(max_side_ratio_bps) => max_side_ratio_bps,
+        None => return Ok(()),
+    };
+    let (side_pool, opposing_pool) = match side {
+        Side::Up => (&round.total_up_bet_amount, &round.total_down_bet_amount),
+        Side::Down => (&round.total_down_bet_amount, &round.total_up_bet_amount),
+    };
+    if opposing_pool.is_empty() {
+        return Ok(());
+    }
+    let prices = bet_denom_prices(deps, config, &round.total_bet_amount, current_time)?;
+    if exceeds_side_ratio(
+        balance_value(side_pool, &prices)?,
+        balance_value(opposing_pool, &prices)?,
+        max_side_ratio_bps,
+    ) {
+        return Err(ContractError::SideImbalanceTooHigh {});
+    }
+    Ok(())
+}
+
+// enables a user to move their bet to the other side before the round starts. the side switch
+// penalty is taken from the bet in every denom and added to the treasury
+pub fn execute_switch_bet_side(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    env: Env,
+    side: Side,
+    round_name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut round = load_round(deps.storage, &round_name)?;
+    match round_status(&round, env.block.time.seconds()) {
+        RoundStatus::Locked | RoundStatus::Settled => {
+            return Err(ContractError::RoundAlreadyStarted {})
+        }
+        RoundStatus::Cancelled => return Err(ContractError::RoundCancelled {}),
+        RoundStatus::Scheduled => return Err(ContractError::RoundNotOpen {}),
+        RoundStatus::Open => {}
+    }
+    let mut bet = bets()
+        .may_load(deps.storage, (round.id, info.sender.clone()))?
+        .ok_or(ContractError::BetNotFound {})?;
+    if bet.side == side {
+        return Err(ContractError::DuplicateBetSide {});
+    }
+    let mut penalty = NativeBalance(
+        bet.amount
+            .0
+            .iter()
+            .map(|coin| Coin {
+                denom: coin.denom.clone(),
+                amount: fee_amount(coin.amount, config.side_switch_penalty_bps),
+            })
+            .collect(),
+    );
+    penalty.normalize();
+    let switched = (bet.amount.clone() - penalty.clone().into_vec())?;
+    match bet.side {
+        Side::Up => {
+            round.up_bets_count -= 1;
+            round.down_bets_count += 1;
+            round.total_up_bet_amount = (round.total_up_bet_amount - bet.amount.into_vec())?;
+            round.total_down_bet_amount += switched.clone();
+        }
+        Side::Down => {
+            round.down_bets_count -= 1;
+            round.up_bets_count += 1;
+            round.total_down_bet_amount = (round.total_down_bet_amount - bet.amount.into_vec())?;
+            round.total_up_bet_amount += switched.clone();
+        }
+    }
+    if !penalty.is_empty() {
+        round.total_bet_amount = (round.total_bet_amount - penalty.clone().into_vec())?;
+        let mut treasury_balance = TREASURYBALANCE.load(deps.storage)?;
+        treasury_balance.balance += penalty.clone();
+        TREASURYBALANCE.save(deps.storage, &treasury_balance)?;
+    }
+    check_round_limits(
+        deps.as_ref(),
+        &config,
+        &round,
+        &side,
+        env.block.time.seconds(),
+    )?;
+    rounds().save(deps.storage, round.id, &round)?;
+    bet.side = side;
+    bet.amount = switched;
+    bets().save(deps.storage, (round.id, info.sender), &bet)?;
+    Ok(Response::new()
+        .add_attribute("action", "switch bet side")
+        .add_attribute("penalty", penalty.to_string()))
+}
+
 pub fn execute_withdraw_bet(
     deps: DepsMut<KujiraQuery>,
     info: MessageInfo,
     env: Env,
     round_name: String,
 ) -> Result<Response, ContractError> {
-    let round = ROUND.load(deps.storage, round_name.clone())?;
-    let withdraw_message: CosmosMsg;
+    let round = load_round(deps.storage, &round_name)?;
     let current_time = env.block.time.seconds();
-    if round.start_time < current_time || round.is_started {
+    if round.start_time < current_time || round.status != RoundStatus::Open {
         return Err(ContractError::RoundAlreadyStarted {});
     }
-    let bet = BET.load(deps.storage, (round_name.clone(), info.sender.clone()))?;
+    let bet = bets().load(deps.storage, (round.id, info.sender.clone()))?;
 
-    let bet_coin = Coin {
-        denom: bet.denom.clone(),
-        amount: Uint128::from(bet.amount),
-    };
+    let bet_coins = bet.amount.into_vec();
 
-    withdraw_message = CosmosMsg::Bank(BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: vec![bet_coin.clone()],
-    });
+    let withdraw_messages = send_coins(&info.sender, bet_coins.clone())?;
     let mut updated_round = round;
     match bet.side {
         Side::Up => {
             updated_round.up_bets_count -= 1;
             updated_round.total_up_bet_amount =
-                (updated_round.total_up_bet_amount - bet_coin.clone()).unwrap();
+                (updated_round.total_up_bet_amount - bet_coins.clone()).unwrap();
         }
         Side::Down => {
             updated_round.down_bets_count -= 1;
             updated_round.total_down_bet_amount =
-                (updated_round.total_down_bet_amount - bet_coin.clone()).unwrap();
+                (updated_round.total_down_bet_amount - bet_coins.clone()).unwrap();
         }
     }
 
-    updated_round.total_bet_amount = (updated_round.total_bet_amount - bet_coin).unwrap();
+    updated_round.total_bet_amount = (updated_round.total_bet_amount - bet_coins).unwrap();
     updated_round.participants_count -= 1;
-    ROUND.save(deps.storage, round_name.clone(), &updated_round)?;
+    rounds().save(deps.storage, updated_round.id, &updated_round)?;
 
-    BET.remove(deps.storage, (round_name, info.sender));
+    bets().remove(deps.storage, (updated_round.id, info.sender))?;
     Ok(Response::new()
         .add_attribute("action", "withdraw bet")
-        .add_message(withdraw_message))
+        .add_messages(withdraw_messages))
 }
 
-// enables an admin to stop a round that is due based on the stop_time
+// enables anyone to stop a round that is due based on the stop_time
+// the keepers that started and stopped the round are paid their share of the round fees
 // name here is the unique name of the round to be stopped
 pub fn execute_stop_round(
     deps: DepsMut<KujiraQuery>,
@@ -329,399 +1472,2694 @@ pub fn execute_stop_round(
     name: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let is_admin = sender_is_admin(&config, &info.sender.as_str())?;
-    if !is_admin {
-        return Err(ContractError::Unauthorized {});
-    }
-    let round = ROUND.load(deps.storage, name.clone())?;
-    if round.is_stopped {
-        return Err(ContractError::RoundAlreadyEnded {});
+    let round = load_round(deps.storage, &name)?;
+    match round.status {
+        RoundStatus::Scheduled | RoundStatus::Open => {
+            return Err(ContractError::RoundNotStarted {})
+        }
+        RoundStatus::Settled => return Err(ContractError::RoundAlreadyEnded {}),
+        RoundStatus::Cancelled => return Err(ContractError::RoundCancelled {}),
+        RoundStatus::Locked => {}
     }
     let current_time = env.block.time.seconds();
     if current_time < round.stop_time {
         return Err(ContractError::RoundStillInProgress {});
     }
-    let q = KujiraQuerier::new(&deps.querier);
-    let res = q.query_exchange_rate(config.asset_denom)?;
-    let price = res.rate;
+    if round_status(&round, current_time) == RoundStatus::Cancelled {
+        return Err(ContractError::RoundStopWindowPassed {});
+    }
+    let (price, settlement_prices) = match stop_prices(deps.as_ref(), &config, &round, current_time)
+    {
+        Ok(prices) => prices,
+        Err(err) if oracle_grace_passed(&config, round.stop_time, current_time) => {
+            return cancel_for_bad_price(deps.storage, &config, &name, round, current_time, err)
+        }
+        Err(err) => return Err(err),
+    };
     let mut stopped_round = round.clone();
-    stopped_round.is_stopped = true;
-    stopped_round.stopped_at = Some(current_time);
+    stopped_round.transition(RoundStatus::Settled, current_time)?;
+    stopped_round.stopped_by = Some(info.sender.clone());
     stopped_round.stop_price = Some(price);
-    ROUND.save(deps.storage, name.clone(), &stopped_round)?;
-    // if the price changed, take fees
-    if round.start_price.unwrap() != price {
+    stopped_round.settlement_prices = settlement_prices;
+    let outcome = round_outcome(&stopped_round)?;
+    stopped_round.outcome = Some(outcome);
+    rounds().save(deps.storage, stopped_round.id, &stopped_round)?;
+    let next_round =
+        open_next_series_round(deps.storage, &config, &name, &stopped_round, current_time)?;
+    let mut messages: Vec<CosmosMsg> = Vec::new();
+    // take fees unless the round is a push and every bet gets refunded
+    if outcome != RoundOutcome::Push {
+        let keepers = [round.started_by.clone(), Some(info.sender)];
+        let mut keeper_rewards: BTreeMap<Addr, NativeBalance> = BTreeMap::new();
         // update the treasury pool amount for each denom used to bet in the round
+        let mut treasury_balance = TREASURYBALANCE.load(deps.storage)?;
         for coin in round.total_bet_amount.into_vec() {
-            let treasury_share = coin.amount.u128() * 15 / 100;
-
-            let mut treasury_balance = TREASURYBALANCE.load(deps.storage)?;
-            let new_coin = Coin {
+            let fee = fee_amount(coin.amount, round.fee_bps);
+            let reward = keeper_reward(fee, config.keeper_reward_bps);
+            let mut treasury_share = fee;
+            if !reward.is_zero() {
+                for keeper in keepers.iter().flatten() {
+                    *keeper_rewards.entry(keeper.clone()).or_default() += Coin {
+                        denom: coin.denom.clone(),
+                        amount: reward,
+                    };
+                    treasury_share -= reward;
+                }
+            }
+            treasury_balance.balance += Coin {
+                amount: treasury_share,
                 denom: coin.denom,
-                amount: Uint128::from(treasury_share),
             };
-            treasury_balance.balance += new_coin;
-            TREASURYBALANCE.save(deps.storage, &treasury_balance)?;
         }
+        treasury_balance.balance.normalize();
+        TREASURYBALANCE.save(deps.storage, &treasury_balance)?;
+        for (keeper, reward) in keeper_rewards {
+            messages.extend(send_coins(&keeper, reward.into_vec())?);
+        }
+    }
+    let mut response = Response::new()
+        .add_attribute("action", "Stop round")
+        .add_messages(messages);
+    if let Some(next_round) = next_round {
+        response = response.add_attribute("next_round", next_round);
     }
-    Ok(Response::new().add_attribute("action", "Stop round"))
+    Ok(response)
 }
 
-// enables a user to claim their win from a given round
-// this function also sends fees from the round to the treasury address if
-// the fees have not been claimed already
+// enables a user to claim their win from a given round, or their stake back if the price
+// did not move. the amounts are computed by the payout module
 pub fn execute_claim_win(
     deps: DepsMut<KujiraQuery>,
     info: MessageInfo,
-    _env: Env,
+    env: Env,
     round_name: String,
 ) -> Result<Response, ContractError> {
-    let round = ROUND.load(deps.storage, round_name.clone())?;
-    let mut messages: Vec<CosmosMsg> = Vec::new();
-    if !round.is_stopped {
-        return Err(ContractError::RoundStillInProgress {});
+    let mut round = load_round(deps.storage, &round_name)?;
+    match round.status {
+        RoundStatus::Settled => {}
+        RoundStatus::Cancelled => return Err(ContractError::RoundCancelled {}),
+        _ => return Err(ContractError::RoundStillInProgress {}),
     }
-
-    let bet = BET.load(deps.storage, (round_name.clone(), info.sender.clone()))?;
-    let start_price = round.start_price.unwrap();
-    let stop_price = round.stop_price.unwrap();
-    let mut is_winner = false;
-    match bet.side {
-        Side::Up => {
-            if stop_price > start_price {
-                is_winner = true
-            }
-        }
-        Side::Down => {
-            if stop_price < start_price {
-                is_winner = true
-            }
-        }
+    if round.claim_window_expired(env.block.time.seconds()) {
+        return Err(ContractError::ClaimWindowExpired {});
     }
-    let mut sender_coins: Vec<Coin> = Vec::new();
-    if is_winner {
-        if bet.win_claimed {
-            return Err(ContractError::WinAlreadyClaimed {});
-        }
-        // give the winner a share of all denoms which were used to bet
-        for coin in round.total_bet_amount.into_vec() {
-            let q = KujiraQuerier::new(&deps.querier);
-            let res = q.query_exchange_rate(coin.denom.to_string())?;
-            let total_amount_in_usd =
-                res.rate * Decimal::from_str(&coin.amount.u128().to_string())?;
-            // sharable amount is 85% of the bets, 15% goes to fees wallet
-            let numerator = Uint128::from(85u128) * total_amount_in_usd;
-            let sharable_amount = numerator.checked_div(Uint128::from(100u128)).unwrap();
-            if round.participants_count == 1 {
-                // if the sender was the only participant he gets 20% of bet
-                // amount back if he wins
-                let win_amount = 20 / 100 * bet.amount;
-                let sender_coin = Coin {
-                    denom: bet.denom.clone(),
-                    amount: Uint128::from(win_amount),
-                };
-                sender_coins.push(sender_coin);
-            } else {
-                let res = q.query_exchange_rate(bet.denom.to_string())?;
-                let user_bet_amount_in_usd = res.rate * Uint128::from(bet.amount);
-                let senders_share = user_bet_amount_in_usd / sharable_amount;
-                let denom_win_amount = senders_share.u128() * bet.amount;
-                let sender_coin = Coin {
-                    denom: coin.denom,
-                    amount: Uint128::from(denom_win_amount),
-                };
-                sender_coins.push(sender_coin);
-            }
-        }
-        let sender_wins_msg = CosmosMsg::Bank(BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: sender_coins,
-        });
 
-        messages.push(sender_wins_msg);
-        let mut updated_bet = bet;
-        updated_bet.win_claimed = true;
-        BET.save(
-            deps.storage,
-            (round_name.clone(), info.sender),
-            &updated_bet,
-        )?;
-    } else if start_price == stop_price {
-        let sender_coin = Coin {
-            denom: bet.denom.clone(),
-            amount: Uint128::from(bet.amount),
-        };
-        sender_coins.push(sender_coin);
-        let prices_equal_msg = CosmosMsg::Bank(BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: sender_coins,
-        });
-        messages.push(prices_equal_msg)
-    } else {
+    let bet = bets().load(deps.storage, (round.id, info.sender.clone()))?;
+    let payout = compute_payout(&round, &bet)?;
+    if payout.kind == PayoutKind::Loss {
         return Err(ContractError::YouLost {});
     }
+    if bet.win_claimed {
+        return Err(ContractError::WinAlreadyClaimed {});
+    }
+    let mut updated_bet = bet;
+    updated_bet.win_claimed = true;
+    bets().save(deps.storage, (round.id, info.sender.clone()), &updated_bet)?;
+    record_claim(deps.storage, &mut round, &payout.coins)?;
+
+    let sender_wins_messages = send_coins(&info.sender, payout.coins)?;
     Ok(Response::new()
         .add_attribute("action", "claim win")
-        .add_messages(messages))
+        .add_messages(sender_wins_messages))
 }
 
-// this enables an admin to withdraw available funds from the treasury pool
-pub fn execute_withdraw_from_treasury_pool(
+// enables anyone to cancel a round that was not started or stopped within its grace period
+// so that its bets can be refunded
+pub fn execute_cancel_round(
     deps: DepsMut<KujiraQuery>,
-    info: MessageInfo,
-    _env: Env,
-    denom: String,
-    to_address: String,
-    amount: u128,
+    env: Env,
+    name: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let is_admin = sender_is_admin(&config, &info.sender.as_str())?;
-    if !is_admin {
-        return Err(ContractError::Unauthorized {});
+    let round = load_round(deps.storage, &name)?;
+    if round.status == RoundStatus::Cancelled {
+        return Err(ContractError::RoundCancelled {});
     }
-    let mut treasury_balance = TREASURYBALANCE.load(deps.storage)?;
-    let message: CosmosMsg;
-
-    let coin = Coin {
-        denom,
-        amount: Uint128::from(amount),
-    };
-    let new_balance = match treasury_balance.balance - coin.clone() {
-        Ok(balance) => balance,
-        Err(_) => return Err(ContractError::InsufficientTreasuryBalance {}),
-    };
-    message = CosmosMsg::Bank(BankMsg::Send {
-        to_address,
-        amount: vec![coin],
-    });
-    treasury_balance.balance = new_balance;
-    TREASURYBALANCE.save(deps.storage, &treasury_balance)?;
-    Ok(Response::new()
-        .add_attribute("action", "Withdraw from treasury pool")
-        .add_message(message))
+    let current_time = env.block.time.seconds();
+    if round_status(&round, current_time) != RoundStatus::Cancelled {
+        return Err(ContractError::RoundNotCancelled {});
+    }
+    cancel_round(deps.storage, &config, &name, round, current_time)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<KujiraQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetRounds {} => query_all_rounds(deps, env),
-        QueryMsg::GetRound { round_name } => query_round(deps, env, round_name),
-        QueryMsg::GetTreasuryBalance {} => query_treasury_balance(deps, env),
-        QueryMsg::GetUserBet {
-            round_name,
-            user_addr,
-        } => query_user_bet(deps, env, round_name, user_addr),
+// cancels a round and opens the next round of its series
+fn cancel_round(
+    storage: &mut dyn Storage,
+    config: &Config,
+    name: &str,
+    mut round: Round,
+    current_time: u64,
+) -> Result<Response, ContractError> {
+    round.transition(RoundStatus::Cancelled, current_time)?;
+    rounds().save(storage, round.id, &round)?;
+    let next_round = open_next_series_round(storage, config, name, &round, current_time)?;
+    let mut response = Response::new().add_attribute("action", "Cancel round");
+    if let Some(next_round) = next_round {
+        response = response.add_attribute("next_round", next_round);
     }
+    Ok(response)
 }
 
-// gets all rounds created in the smart contract
-pub fn query_all_rounds(deps: Deps<KujiraQuery>, _env: Env) -> StdResult<Binary> {
-    let rounds = ROUND
-        .range(deps.storage, None, None, Order::Ascending)
-        .map(|p| Ok(p?.1))
-        .collect::<StdResult<Vec<_>>>()?;
-    to_binary(&AllRoundsResponse { rounds })
+// gets the price of an asset from its price source, a failed query or a zero price is reported
+// as a bad oracle price
+fn asset_price(
+    deps: Deps<KujiraQuery>,
+    config: &Config,
+    denom: &str,
+    current_time: u64,
+) -> Result<Decimal, ContractError> {
+    let price = query_price(deps, config, denom, current_time).map_err(|err| {
+        ContractError::OracleUnavailable {
+            message: err.to_string(),
+        }
+    })?;
+    if price.is_zero() {
+        return Err(ContractError::ZeroOraclePrice {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(price)
 }
 
-// gets single treasury pool denom
-pub fn query_treasury_balance(deps: Deps<KujiraQuery>, _env: Env) -> StdResult<Binary> {
-    let treasury_balance = TREASURYBALANCE.may_load(deps.storage)?;
-    to_binary(&TreasuryBalanceResponse { treasury_balance })
+// gets the stop price of a round and the prices of its bet denoms. a stop price that moved more
+// than the max price deviation is rejected like any other bad oracle price
+fn stop_prices(
+    deps: Deps<KujiraQuery>,
+    config: &Config,
+    round: &Round,
+    current_time: u64,
+) -> Result<(Decimal, Vec<DenomPrice>), ContractError> {
+    let price = asset_price(deps, config, &round.asset_denom, current_time)?;
+    if let (Some(start_price), Some(max_price_deviation_bps)) =
+        (round.start_price, config.max_price_deviation_bps)
+    {
+        if exceeds_price_deviation(start_price, price, max_price_deviation_bps) {
+            return Err(ContractError::PriceDeviationTooHigh {
+                max_price_deviation_bps,
+            });
+        }
+    }
+    // record the bet denom prices so every claim splits the pool with the same values
+    let settlement_prices = bet_denom_prices(deps, config, &round.total_bet_amount, current_time)
+        .map_err(|err| ContractError::OracleUnavailable {
+        message: err.to_string(),
+    })?;
+    if let Some(zero) = settlement_prices.iter().find(|p| p.price.is_zero()) {
+        return Err(ContractError::ZeroOraclePrice {
+            denom: zero.denom.clone(),
+        });
+    }
+    Ok((price, settlement_prices))
 }
 
-// gets single round by name
-pub fn query_round(deps: Deps<KujiraQuery>, _env: Env, round_name: String) -> StdResult<Binary> {
-    let round = ROUND.may_load(deps.storage, round_name)?;
-    to_binary(&RoundResponse { round })
+// returns true once a round has waited longer than the oracle grace period for a good price
+// after the time it was due to be started or stopped
+fn oracle_grace_passed(config: &Config, due_time: u64, current_time: u64) -> bool {
+    config.oracle_grace_period.map_or(false, |grace_period| {
+        current_time > due_time.saturating_add(grace_period)
+    })
 }
 
-// gets bets placed by a given user in a given round
-pub fn query_user_bet(
-    deps: Deps<KujiraQuery>,
-    _env: Env,
-    round_name: String,
-    user_addr: String,
-) -> StdResult<Binary> {
-    let validated_user_addr = deps.api.addr_validate(&user_addr)?;
-    let bet = BET.may_load(deps.storage, (round_name, validated_user_addr))?;
-    to_binary(&UserBetResponse { bet })
+// cancels a round that could not get a good oracle price within the oracle grace period so its
+// bets can be refunded right away instead of waiting for its start or stop window to pass
+fn cancel_for_bad_price(
+    storage: &mut dyn Storage,
+    config: &Config,
+    name: &str,
+    round: Round,
+    current_time: u64,
+    err: ContractError,
+) -> Result<Response, ContractError> {
+    let response = cancel_round(storage, config, name, round, current_time)?;
+    Ok(response.add_attribute("reason", err.to_string()))
 }
 
-#[cfg(test)]
-mod tests {
-
-    use crate::contract::{execute, instantiate, query};
-    use crate::msg::{
-        AllRoundsResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RoundResponse,
-        TreasuryBalanceResponse, UserBetResponse,
-    };
-    use crate::state::{Bet, Round, Side, TreasuryBalance};
-    use crate::ContractError;
-    use core::cell::RefCell;
-    use core::marker::PhantomData;
-    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
-    use cosmwasm_std::{
-        attr, from_binary, to_binary, Coin, ContractResult, Decimal, OwnedDeps, StdError,
-        SystemResult, Timestamp, Uint128,
-    };
-    use cw_utils::NativeBalance;
-    use kujira::query::{ExchangeRateResponse, KujiraQuery, OracleQuery};
-    use std::collections::HashMap;
-
-    use std::str::FromStr;
-    use std::time::{Duration, SystemTime, UNIX_EPOCH};
-
-    pub const ADMIN1: &str = "addr1";
-    pub const ADMIN2: &str = "addr2";
-    pub const ANYONE: &str = "anyone";
+// enables a user to get their full stake back from a cancelled round, no fee is taken.
+// a round that missed its start or stop grace period is cancelled on the first refund
+pub fn execute_claim_refund(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    env: Env,
+    round_name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut round = load_round(deps.storage, &round_name)?;
+    let current_time = env.block.time.seconds();
+    if round_status(&round, current_time) != RoundStatus::Cancelled {
+        return Err(ContractError::RoundNotCancelled {});
+    }
+    cancel_stale_round(deps.storage, &config, &mut round, current_time)?;
+    if round.claim_window_expired(current_time) {
+        return Err(ContractError::ClaimWindowExpired {});
+    }
 
-    pub const USER1: &str = "user1";
+    let mut bet = bets().load(deps.storage, (round.id, info.sender.clone()))?;
+    if bet.win_claimed {
+        return Err(ContractError::RefundAlreadyClaimed {});
+    }
+    bet.win_claimed = true;
+    bets().save(deps.storage, (round.id, info.sender.clone()), &bet)?;
+    let refund = bet.amount.into_vec();
+    record_claim(deps.storage, &mut round, &refund)?;
 
-    pub const TREASURY: &str = "treasury1";
+    let refund_messages = send_coins(&info.sender, refund)?;
+    Ok(Response::new()
+        .add_attribute("action", "claim refund")
+        .add_messages(refund_messages))
+}
 
-    pub const ASSETDENOM: &str = "asset1";
-    pub const ASSETDENOM2: &str = "asset2";
+// cancels a round that missed its start or stop grace period but has not been cancelled yet
+fn cancel_stale_round(
+    storage: &mut dyn Storage,
+    config: &Config,
+    round: &mut Round,
+    current_time: u64,
+) -> Result<(), ContractError> {
+    if round.status == RoundStatus::Cancelled
+        || round_status(round, current_time) != RoundStatus::Cancelled
+    {
+        return Ok(());
+    }
+    round.transition(RoundStatus::Cancelled, current_time)?;
+    rounds().save(storage, round.id, round)?;
+    open_next_series_round(storage, config, &round.name, round, current_time)?;
+    Ok(())
+}
 
-    pub const DENOM1: &str = "denom1";
-    pub const DENOM2: &str = "denom2";
-    pub const DENOM3: &str = "denom3";
+// enables a user to claim from several rounds at once, wins and refunds are paid together in
+// a single transfer. lost bets, bets already claimed and rounds still running are skipped
+pub fn execute_claim_wins(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    env: Env,
+    round_names: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let current_time = env.block.time.seconds();
+    let mut claimed = NativeBalance::default();
+    let mut claimed_rounds = 0;
+    for round_name in round_names {
+        let mut round = load_round(deps.storage, &round_name)?;
+        if !bets().has(deps.storage, (round.id, info.sender.clone())) {
+            return Err(ContractError::BetNotFound {});
+        }
+        let coins = claim_from_round(
+            deps.storage,
+            &config,
+            &mut round,
+            &info.sender,
+            current_time,
+        )?;
+        if !coins.is_empty() {
+            claimed_rounds += 1;
+        }
+        for coin in coins {
+            claimed += coin;
+        }
+    }
+    claims_response(info.sender, claimed, claimed_rounds)
+}
+
+// enables a user to claim from every finished round they bet in without listing them, oldest
+// rounds first. the sender's bets are scanned a page of limit bets at a time, 10 by default and
+// at most 30. while there are more bets the response has a next_start_after attribute to pass
+// as start_after for the next page
+pub fn execute_claim_all(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let current_time = env.block.time.seconds();
+    let limit = limit.unwrap_or(DEFAULT_PAYOUT_LIMIT).min(MAX_PAYOUT_LIMIT) as usize;
+    let min = start_after.map(|round_id| Bound::exclusive((round_id, info.sender.clone())));
+    let user_bets = bets()
+        .idx
+        .user
+        .prefix(info.sender.clone())
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let next_start_after = if user_bets.len() == limit {
+        user_bets.last().map(|((round_id, _), _)| *round_id)
+    } else {
+        None
+    };
+    let mut claimed = NativeBalance::default();
+    let mut claimed_rounds = 0;
+    for ((round_id, _), bet) in user_bets {
+        if bet.win_claimed || bet.lost {
+            continue;
+        }
+        let mut round = rounds().load(deps.storage, round_id)?;
+        let coins = claim_from_round(
+            deps.storage,
+            &config,
+            &mut round,
+            &info.sender,
+            current_time,
+        )?;
+        if !coins.is_empty() {
+            claimed_rounds += 1;
+        }
+        for coin in coins {
+            claimed += coin;
+        }
+    }
+    // a page with nothing to claim only fails once there are no more bets to scan
+    let response = if claimed.is_empty() && next_start_after.is_some() {
+        Response::new()
+            .add_attribute("action", "claim wins")
+            .add_attribute("claimed_rounds", "0")
+    } else {
+        claims_response(info.sender, claimed, claimed_rounds)?
+    };
+    Ok(match next_start_after {
+        Some(round_id) => response.add_attribute("next_start_after", round_id.to_string()),
+        None => response,
+    })
+}
+
+// enables anyone to pay out a settled or cancelled round so bettors don't have to claim.
+// bets are paid in pages of limit bets, 10 by default and at most 30, and every call picks up
+// after the last bet paid by the previous one. bets that were already claimed are skipped
+pub fn execute_distribute_round(
+    deps: DepsMut<KujiraQuery>,
+    env: Env,
+    round_name: String,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let current_time = env.block.time.seconds();
+    let mut round = load_round(deps.storage, &round_name)?;
+    cancel_stale_round(deps.storage, &config, &mut round, current_time)?;
+    if !matches!(round.status, RoundStatus::Settled | RoundStatus::Cancelled) {
+        return Err(ContractError::RoundStillInProgress {});
+    }
+    let mut distribution = DISTRIBUTION
+        .may_load(deps.storage, round.id)?
+        .unwrap_or(Distribution {
+            last_user: None,
+            finished: false,
+        });
+    if distribution.finished {
+        return Err(ContractError::RoundAlreadyDistributed {});
+    }
+    let limit = limit.unwrap_or(DEFAULT_PAYOUT_LIMIT).min(MAX_PAYOUT_LIMIT) as usize;
+    let users = bets()
+        .prefix(round.id)
+        .keys(
+            deps.storage,
+            distribution.last_user.clone().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut messages: Vec<CosmosMsg> = Vec::new();
+    let mut paid_bets = 0;
+    for user in users.iter() {
+        let coins = claim_from_round(deps.storage, &config, &mut round, user, current_time)?;
+        if !coins.is_empty() {
+            paid_bets += 1;
+            messages.extend(send_coins(user, coins)?);
+        }
+    }
+    distribution.finished = users.len() < limit;
+    if let Some(user) = users.last() {
+        distribution.last_user = Some(user.clone());
+    }
+    DISTRIBUTION.save(deps.storage, round.id, &distribution)?;
+    Ok(Response::new()
+        .add_attribute("action", "Distribute round")
+        .add_attribute("paid_bets", paid_bets.to_string())
+        .add_attribute("finished", distribution.finished.to_string())
+        .add_messages(messages))
+}
+
+// marks a user's bet in a round as claimed and returns what it pays. a lost bet is marked as
+// lost and pays nothing. nothing is paid either for a bet that was already claimed, a round
+// that has not finished yet or a round whose claim window is over
+fn claim_from_round(
+    storage: &mut dyn Storage,
+    config: &Config,
+    round: &mut Round,
+    user: &Addr,
+    current_time: u64,
+) -> Result<Vec<Coin>, ContractError> {
+    cancel_stale_round(storage, config, round, current_time)?;
+    if round.claim_window_expired(current_time) {
+        return Ok(vec![]);
+    }
+    let mut bet = bets().load(storage, (round.id, user.clone()))?;
+    if bet.win_claimed {
+        return Ok(vec![]);
+    }
+    let payout = match bet_payout(round, &bet, round.status)? {
+        Some(payout) if payout.kind == PayoutKind::Loss => {
+            bet.lost = true;
+            bets().save(storage, (round.id, user.clone()), &bet)?;
+            return Ok(vec![]);
+        }
+        Some(payout) => payout,
+        None => return Ok(vec![]),
+    };
+    bet.win_claimed = true;
+    bets().save(storage, (round.id, user.clone()), &bet)?;
+    record_claim(storage, round, &payout.coins)?;
+    Ok(payout.coins)
+}
+
+// adds what was paid to a bettor to the amount claimed from the round
+fn record_claim(
+    storage: &mut dyn Storage,
+    round: &mut Round,
+    coins: &[Coin],
+) -> Result<(), ContractError> {
+    for coin in coins {
+        round.claimed_amount += coin.clone();
+    }
+    round.claimed_amount.normalize();
+    rounds().save(storage, round.id, round)?;
+    Ok(())
+}
+
+// enables the trusted reporter of a denom to post its current price
+pub fn execute_report_price(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    env: Env,
+    denom: String,
+    price: Decimal,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    match config.price_source(&denom) {
+        PriceSource::Reporter { reporter, .. } if reporter == info.sender => {}
+        _ => return Err(ContractError::Unauthorized {}),
+    }
+    if price.is_zero() {
+        return Err(ContractError::ZeroOraclePrice { denom });
+    }
+    let reported = ReportedPrice {
+        price,
+        reported_at: env.block.time.seconds(),
+    };
+    REPORTED_PRICES.save(deps.storage, denom, &reported)?;
+    Ok(Response::new()
+        .add_attribute("action", "report price")
+        .add_attribute("price", price.to_string()))
+}
+
+// enables anyone to move what is left unclaimed in a round to the treasury once its claim
+// window is over. the swept amount is recorded on the round and nothing can be claimed after
+pub fn execute_sweep_expired(
+    deps: DepsMut<KujiraQuery>,
+    env: Env,
+    round_name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let current_time = env.block.time.seconds();
+    let mut round = load_round(deps.storage, &round_name)?;
+    cancel_stale_round(deps.storage, &config, &mut round, current_time)?;
+    if !round.claim_window_expired(current_time) {
+        return Err(ContractError::ClaimWindowNotExpired {});
+    }
+    if round.swept_amount.is_some() {
+        return Err(ContractError::RoundAlreadySwept {});
+    }
+    let mut unclaimed = round.total_bet_amount.clone();
+    // the fee was already taken when the round was stopped
+    if round.status == RoundStatus::Settled && round.outcome != Some(RoundOutcome::Push) {
+        for coin in round.total_bet_amount.0.iter() {
+            unclaimed = unclaimed.sub_saturating(Coin {
+                denom: coin.denom.clone(),
+                amount: fee_amount(coin.amount, round.fee_bps),
+            })?;
+        }
+    }
+    for coin in round.claimed_amount.0.iter() {
+        unclaimed = unclaimed.sub_saturating(coin.clone())?;
+    }
+    unclaimed.normalize();
+    let mut treasury_balance = TREASURYBALANCE.load(deps.storage)?;
+    treasury_balance.balance += unclaimed.clone();
+    treasury_balance.balance.normalize();
+    TREASURYBALANCE.save(deps.storage, &treasury_balance)?;
+    round.swept_amount = Some(unclaimed.clone());
+    rounds().save(deps.storage, round.id, &round)?;
+    Ok(Response::new()
+        .add_attribute("action", "Sweep expired")
+        .add_attribute("swept_amount", unclaimed.to_string()))
+}
+
+// pays everything claimed from several rounds in a single transfer
+fn claims_response(
+    user: Addr,
+    mut claimed: NativeBalance,
+    claimed_rounds: usize,
+) -> Result<Response, ContractError> {
+    claimed.normalize();
+    if claimed.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    let claim_messages = send_coins(&user, claimed.into_vec())?;
+    Ok(Response::new()
+        .add_attribute("action", "claim wins")
+        .add_attribute("claimed_rounds", claimed_rounds.to_string())
+        .add_messages(claim_messages))
+}
+
+// this enables an admin to withdraw available funds from the treasury pool
+pub fn execute_withdraw_from_treasury_pool(
+    deps: DepsMut<KujiraQuery>,
+    info: MessageInfo,
+    _env: Env,
+    denom: String,
+    to_address: String,
+    amount: u128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let is_admin = sender_is_admin(&config, info.sender.as_str())?;
+    if !is_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut treasury_balance = TREASURYBALANCE.load(deps.storage)?;
+
+    let coin = Coin {
+        denom,
+        amount: Uint128::from(amount),
+    };
+    let new_balance = match treasury_balance.balance - coin.clone() {
+        Ok(balance) => balance,
+        Err(_) => return Err(ContractError::InsufficientTreasuryBalance {}),
+    };
+    let messages = send_coins(to_address, vec![coin])?;
+    treasury_balance.balance = new_balance;
+    TREASURYBALANCE.save(deps.storage, &treasury_balance)?;
+    Ok(Response::new()
+        .add_attribute("action", "Withdraw from treasury pool")
+        .add_messages(messages))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<KujiraQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetRounds {
+            start_after,
+            limit,
+            order,
+            filter,
+        } => query_all_rounds(deps, env, start_after, limit, order, filter),
+        QueryMsg::GetRound { round_name } => query_round(deps, env, round_name),
+        QueryMsg::GetRoundSeries { name } => query_round_series(deps, env, name),
+        QueryMsg::GetUserBets {
+            user_addr,
+            start_after,
+            limit,
+        } => query_user_bets(deps, env, user_addr, start_after, limit),
+        QueryMsg::GetClaimable {
+            round_name,
+            user_addr,
+        } => query_claimable(deps, env, round_name, user_addr),
+        QueryMsg::GetRoundOdds { round_name } => query_round_odds(deps, env, round_name),
+        QueryMsg::GetRoundBets {
+            round_name,
+            side,
+            start_after,
+            limit,
+        } => query_round_bets(deps, env, round_name, side, start_after, limit),
+        QueryMsg::GetConfig {} => query_config(deps, env),
+        QueryMsg::GetTreasuryBalance {} => query_treasury_balance(deps, env),
+        QueryMsg::GetUserBet {
+            round_name,
+            user_addr,
+        } => query_user_bet(deps, env, round_name, user_addr),
+    }
+}
+
+// gets rounds created in the smart contract a page at a time, oldest first by default.
+// start_after is the id of the last round of the previous page, limit defaults to 10 rounds
+// and can't be more than 30. limit is the most rounds scanned for a page and the filter is
+// applied to those, so a page can hold fewer rounds while next_start_after is set
+pub fn query_all_rounds(
+    deps: Deps<KujiraQuery>,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    order: Option<RoundOrder>,
+    filter: Option<RoundFilter>,
+) -> StdResult<Binary> {
+    let current_time = env.block.time.seconds();
+    let filter = filter.unwrap_or_default();
+    let creator = filter
+        .creator
+        .map(|creator| deps.api.addr_validate(&creator))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start_after = start_after.map(Bound::exclusive);
+    let (min, max, order) = match order.unwrap_or(RoundOrder::OldestFirst) {
+        RoundOrder::OldestFirst => (start_after, None, Order::Ascending),
+        RoundOrder::NewestFirst => (None, start_after, Order::Descending),
+    };
+    let matches_filter = |round: &Round| {
+        let status_matches = filter
+            .status
+            .map_or(true, |status| round_status(round, current_time) == status);
+        let asset_matches = filter
+            .asset_denom
+            .as_ref()
+            .map_or(true, |asset_denom| &round.asset_denom == asset_denom);
+        let creator_matches = creator
+            .as_ref()
+            .map_or(true, |creator| &round.creator == creator);
+        status_matches && asset_matches && creator_matches
+    };
+    let scanned_rounds = rounds()
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|p| Ok(p?.1))
+        .collect::<StdResult<Vec<Round>>>()?;
+    let next_start_after = if scanned_rounds.len() == limit {
+        scanned_rounds.last().map(|round| round.id)
+    } else {
+        None
+    };
+    let rounds = scanned_rounds.into_iter().filter(matches_filter).collect();
+    to_binary(&AllRoundsResponse {
+        rounds,
+        next_start_after,
+    })
+}
+
+// gets the contract config, including the accepted bet denoms and their bet limits
+pub fn query_config(deps: Deps<KujiraQuery>, _env: Env) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_binary(&ConfigResponse { config })
+}
+
+// gets single treasury pool denom
+pub fn query_treasury_balance(deps: Deps<KujiraQuery>, _env: Env) -> StdResult<Binary> {
+    let treasury_balance = TREASURYBALANCE.may_load(deps.storage)?;
+    to_binary(&TreasuryBalanceResponse { treasury_balance })
+}
+
+// gets single round by name
+pub fn query_round(deps: Deps<KujiraQuery>, env: Env, round_name: String) -> StdResult<Binary> {
+    let round = may_load_round(deps.storage, &round_name)?;
+    let status = round
+        .as_ref()
+        .map(|round| round_status(round, env.block.time.seconds()));
+    to_binary(&RoundResponse { round, status })
+}
+
+// gets single round series by name
+pub fn query_round_series(deps: Deps<KujiraQuery>, _env: Env, name: String) -> StdResult<Binary> {
+    let series = ROUND_SERIES.may_load(deps.storage, name)?;
+    to_binary(&RoundSeriesResponse { series })
+}
+
+// gets bets placed by a given user in a given round
+pub fn query_user_bet(
+    deps: Deps<KujiraQuery>,
+    _env: Env,
+    round_name: String,
+    user_addr: String,
+) -> StdResult<Binary> {
+    let validated_user_addr = deps.api.addr_validate(&user_addr)?;
+    let bet = match may_load_round(deps.storage, &round_name)? {
+        Some(round) => bets().may_load(deps.storage, (round.id, validated_user_addr))?,
+        None => None,
+    };
+    to_binary(&UserBetResponse { bet })
+}
+
+// gets the bets placed by a user across rounds a page at a time, ordered by round id.
+// start_after is the id of the round of the last bet of the previous page, limit defaults to
+// 10 bets and can't be more than 30
+pub fn query_user_bets(
+    deps: Deps<KujiraQuery>,
+    env: Env,
+    user_addr: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let current_time = env.block.time.seconds();
+    let user = deps.api.addr_validate(&user_addr)?;
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let min = start_after.map(|round_id| Bound::exclusive((round_id, user.clone())));
+    let user_bets = bets()
+        .idx
+        .user
+        .prefix(user)
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let ((round_id, _), bet) = item?;
+            let round = rounds().load(deps.storage, round_id)?;
+            let round_status = round_status(&round, current_time);
+            let claimable = if round.claim_window_expired(current_time) {
+                vec![]
+            } else {
+                claimable_coins(&round, &bet, round_status)?
+            };
+            Ok(UserRoundBet {
+                round_id,
+                round_name: round.name,
+                round_status,
+                bet,
+                claimable,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let next_start_after = if user_bets.len() == limit {
+        user_bets.last().map(|user_bet| user_bet.round_id)
+    } else {
+        None
+    };
+    to_binary(&UserBetsResponse {
+        bets: user_bets,
+        next_start_after,
+    })
+}
+
+// previews what a user gets from claiming a round using the same payout as claiming does,
+// a cancelled round is claimed with ClaimRefund and any other round with ClaimWin
+pub fn query_claimable(
+    deps: Deps<KujiraQuery>,
+    env: Env,
+    round_name: String,
+    user_addr: String,
+) -> StdResult<Binary> {
+    let user = deps.api.addr_validate(&user_addr)?;
+    let round = load_round(deps.storage, &round_name)?;
+    let bet = bets().load(deps.storage, (round.id, user))?;
+    let round_status = round_status(&round, env.block.time.seconds());
+    let payout = bet_payout(&round, &bet, round_status)?;
+    let (kind, coins) = match payout {
+        Some(payout) => (Some(payout.kind), payout.coins),
+        None => (None, vec![]),
+    };
+    to_binary(&ClaimableResponse {
+        round_status,
+        kind,
+        coins,
+        claimed: bet.win_claimed,
+        claim_window_expired: round.claim_window_expired(env.block.time.seconds()),
+    })
+}
+
+// gets the value of both sides of a round and what a winning bet gets back per unit of value,
+// using the same prices and fee as the payout once the round is settled
+pub fn query_round_odds(
+    deps: Deps<KujiraQuery>,
+    env: Env,
+    round_name: String,
+) -> StdResult<Binary> {
+    let round = load_round(deps.storage, &round_name)?;
+    let prices = if round.status == RoundStatus::Settled {
+        round.settlement_prices.clone()
+    } else {
+        let config = CONFIG.load(deps.storage)?;
+        bet_denom_prices(
+            deps,
+            &config,
+            &round.total_bet_amount,
+            env.block.time.seconds(),
+        )?
+    };
+    let up_value = balance_value(&round.total_up_bet_amount, &prices)?;
+    let down_value = balance_value(&round.total_down_bet_amount, &prices)?;
+    let up = SideOdds {
+        pool: round.total_up_bet_amount.into_vec(),
+        value: Decimal256::new(up_value),
+        multiplier: payout_multiplier(up_value, down_value, round.fee_bps)?,
+    };
+    let down = SideOdds {
+        pool: round.total_down_bet_amount.into_vec(),
+        value: Decimal256::new(down_value),
+        multiplier: payout_multiplier(down_value, up_value, round.fee_bps)?,
+    };
+    to_binary(&RoundOddsResponse { up, down, prices })
+}
+
+// gets the bets placed in a round a page at a time, ordered by user address.
+// start_after is the address of the last user of the previous page, limit defaults to 10 bets
+// and can't be more than 30. side only returns the bets on that side out of the limit bets
+// scanned for a page, so a page can hold fewer bets while next_start_after is set
+pub fn query_round_bets(
+    deps: Deps<KujiraQuery>,
+    _env: Env,
+    round_name: String,
+    side: Option<Side>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let round = load_round(deps.storage, &round_name)?;
+    let start_after = start_after
+        .map(|user| deps.api.addr_validate(&user))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let scanned_bets = bets()
+        .prefix(round.id)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let next_start_after = if scanned_bets.len() == limit {
+        scanned_bets.last().map(|(user, _)| user.to_string())
+    } else {
+        None
+    };
+    let round_bets = scanned_bets
+        .into_iter()
+        .filter(|(_, bet)| side.as_ref().map_or(true, |side| &bet.side == side))
+        .map(|(user, bet)| RoundBet { user, bet })
+        .collect();
+    to_binary(&RoundBetsResponse {
+        bets: round_bets,
+        next_start_after,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::contract::{execute, instantiate, migrate, query};
+    use crate::helpers::cw20_denom;
+    use crate::msg::{
+        AcceptedCw20Token, AllRoundsResponse, AssetPriceSource, ClaimableResponse, ConfigResponse,
+        ExecuteMsg, InstantiateMsg, MigrateMsg, PriceSourceMsg, QueryMsg, ReceiveMsg,
+        RoundBetsResponse, RoundFilter, RoundOddsResponse, RoundOrder, RoundResponse,
+        RoundSeriesResponse, TreasuryBalanceResponse, UserBetResponse, UserBetsResponse,
+    };
+    use crate::payout::{PayoutKind, DEFAULT_FEE_BPS};
+    use crate::state::{
+        bets, rounds, Bet, BetDenom, LegacyConfig, LegacyRound, Round, RoundOutcome, RoundStatus,
+        Side, SingleDenomBet, TreasuryBalance, CONFIG, DEFAULT_CLAIM_WINDOW,
+        DEFAULT_START_GRACE_PERIOD, DEFAULT_STOP_GRACE_PERIOD, LEGACY_BET, LEGACY_CONFIG,
+        LEGACY_ROUND, TREASURYBALANCE,
+    };
+    use crate::ContractError;
+    use core::cell::RefCell;
+    use core::marker::PhantomData;
+    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        attr, from_binary, to_binary, Addr, BankMsg, Coin, ContractResult, CosmosMsg, Decimal,
+        Decimal256, Deps, Order, OwnedDeps, StdError, SystemResult, Timestamp, Uint128, Uint256,
+        WasmMsg, WasmQuery,
+    };
+    use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+    use cw_utils::NativeBalance;
+    use kujira::fin::{BookResponse, PoolResponse};
+    use kujira::query::{ExchangeRateResponse, KujiraQuery, OracleQuery};
+    use std::collections::HashMap;
+
+    use std::str::FromStr;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    pub const ADMIN1: &str = "addr1";
+    pub const ADMIN2: &str = "addr2";
+    pub const ANYONE: &str = "anyone";
+
+    pub const USER1: &str = "user1";
+
+    pub const KEEPER1: &str = "keeper1";
+    pub const KEEPER2: &str = "keeper2";
+
+    pub const TREASURY: &str = "treasury1";
+
+    pub const ASSETDENOM: &str = "asset1";
+    pub const ASSETDENOM2: &str = "asset2";
+
+    pub const DENOM1: &str = "denom1";
+    pub const DENOM2: &str = "denom2";
+    pub const DENOM3: &str = "denom3";
+
+    pub const CW20TOKEN: &str = "cw20token";
+    pub const FINPAIR: &str = "finpair";
+    pub const REPORTER: &str = "reporter1";
 
     thread_local! {
         static PRICES: RefCell<HashMap<String, Decimal>> = RefCell::new(HashMap::new());
     }
 
-    type OwnedDepsType = OwnedDeps<MockStorage, MockApi, MockQuerier<KujiraQuery>, KujiraQuery>;
+    type OwnedDepsType = OwnedDeps<MockStorage, MockApi, MockQuerier<KujiraQuery>, KujiraQuery>;
+
+    pub fn mock_dependencies_kujira() -> OwnedDepsType {
+        let querier = MockQuerier::new(&[]).with_custom_handler(|query| match query {
+            // KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom }) => {
+            //     let price = PRICES.with(|p| *p.borrow().get(denom.as_str()).unwrap());
+            //     SystemResult::Ok(ContractResult::Ok(
+            //         to_binary(&ExchangeRateResponse { rate: price }).unwrap(),
+            //     ))
+            // }
+            KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
+                let exchange_rate_response = ExchangeRateResponse {
+                    rate: Decimal::from_str("1.23").unwrap(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&exchange_rate_response).unwrap(),
+                ))
+            }
+            _ => panic!("Unexpected query: {query:?}"),
+        });
+
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier,
+            custom_query_type: PhantomData,
+        }
+    }
+
+    pub fn default_instantiate_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
+            asset_denoms: vec![ASSETDENOM.to_string(), ASSETDENOM2.to_string()],
+            accepted_bet_denoms: vec![BetDenom::unlimited(DENOM1), BetDenom::unlimited(DENOM2)],
+            fee_bps: None,
+            min_round_duration: None,
+            max_round_duration: None,
+            min_lead_time: None,
+            start_grace_period: None,
+            stop_grace_period: None,
+            keeper_reward_bps: None,
+            claim_window: None,
+            side_switch_penalty_bps: None,
+            accepted_cw20_tokens: None,
+            pool_caps: None,
+            max_side_ratio_bps: None,
+            max_price_deviation_bps: None,
+            oracle_grace_period: None,
+            price_sources: None,
+        }
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = InstantiateMsg {
+            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
+            asset_denoms: vec![ASSETDENOM.to_string(), ASSETDENOM2.to_string()],
+            accepted_bet_denoms: vec![BetDenom::unlimited(DENOM1), BetDenom::unlimited(DENOM2)],
+            fee_bps: None,
+            min_round_duration: None,
+            max_round_duration: None,
+            min_lead_time: None,
+            start_grace_period: None,
+            stop_grace_period: None,
+            keeper_reward_bps: None,
+            claim_window: None,
+            side_switch_penalty_bps: None,
+            accepted_cw20_tokens: None,
+            pool_caps: None,
+            max_side_ratio_bps: None,
+            max_price_deviation_bps: None,
+            oracle_grace_period: None,
+            price_sources: None,
+        };
+
+        let res = instantiate(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(res.attributes, vec![attr("action", "instantiate")])
+    }
+
+    #[test]
+    fn test_execute_update_admins() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        //
+        let msg = ExecuteMsg::UpdateAdmins {
+            admins: vec![ADMIN1.to_string(), ADMIN2.to_string(), ANYONE.to_string()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(res.attributes, vec![attr("action", "update admins")])
+    }
+
+    #[test]
+    fn test_execute_update_asset_denoms() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::UpdateAssetDenoms {
+            asset_denoms: vec![ASSETDENOM2.to_string()],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(res.attributes, vec![attr("action", "update asset denoms")])
+    }
+
+    #[test]
+    fn test_execute_update_accepted_bet_denom() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::UpdateAcceptedBetDenoms {
+            accepted_bet_denoms: vec![
+                BetDenom::unlimited(DENOM1),
+                BetDenom::unlimited(DENOM2),
+                BetDenom::unlimited(DENOM3),
+            ],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(
+            res.attributes,
+            vec![attr("action", "update accepted bet denoms")]
+        )
+    }
+
+    #[test]
+    fn test_bet_limits() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let limits = BetDenom {
+            denom: DENOM1.to_string(),
+            min_bet: Uint128::from(100u128),
+            max_bet: Some(Uint128::from(1000u128)),
+        };
+        let msg = ExecuteMsg::UpdateAcceptedBetDenoms {
+            accepted_bet_denoms: vec![BetDenom {
+                max_bet: Some(Uint128::from(10u128)),
+                ..limits.clone()
+            }],
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidBetLimits { .. }));
+
+        let msg = ExecuteMsg::UpdateAcceptedBetDenoms {
+            accepted_bet_denoms: vec![limits.clone(), BetDenom::unlimited(DENOM2)],
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = QueryMsg::GetConfig {};
+        let res: ConfigResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.config.accepted_bet_denoms[0], limits);
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: env.block.time.seconds() + 300,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let place_bet = |amount: u128| {
+            let info = mock_info(
+                USER1,
+                &[Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            );
+            let msg = ExecuteMsg::PlaceBet {
+                side: Side::Up,
+                round_name: "Round1".to_string(),
+            };
+            (info, msg)
+        };
+        let (bettor, msg) = place_bet(50);
+        let err = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap_err();
+        assert!(matches!(err, ContractError::BetTooSmall { .. }));
+        let (bettor, msg) = place_bet(600);
+        let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        // the maximum applies to the bet including top ups
+        let (bettor, msg) = place_bet(500);
+        let err = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap_err();
+        assert!(matches!(err, ContractError::BetTooLarge { .. }));
+        let (bettor, msg) = place_bet(50);
+        let _res = execute(deps.as_mut(), env, bettor, msg).unwrap();
+    }
+
+    #[test]
+    fn test_round_limits() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let mut msg = default_instantiate_msg();
+        msg.max_side_ratio_bps = Some(5_000);
+        let err = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::SideRatioTooLow { .. }));
+        msg.pool_caps = Some(vec![Coin {
+            denom: DENOM1.to_string(),
+            amount: Uint128::from(1000u128),
+        }]);
+        msg.max_side_ratio_bps = Some(20_000);
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: env.block.time.seconds() + 300,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // rounds keep the limits they were created with
+        let msg = ExecuteMsg::UpdateRoundLimits {
+            pool_caps: vec![],
+            max_side_ratio_bps: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let place_bet = |user: &str, side: Side, amount: u128| {
+            let info = mock_info(
+                user,
+                &[Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            );
+            let msg = ExecuteMsg::PlaceBet {
+                side,
+                round_name: "Round1".to_string(),
+            };
+            (info, msg)
+        };
+        // a side can grow as much as it likes while the other side is empty
+        let (bettor, msg) = place_bet(USER1, Side::Up, 600);
+        let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        let (bettor, msg) = place_bet("user2", Side::Down, 200);
+        let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        let (bettor, msg) = place_bet("user3", Side::Up, 200);
+        let err = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap_err();
+        assert!(matches!(err, ContractError::SideImbalanceTooHigh {}));
+        let (bettor, msg) = place_bet("user4", Side::Down, 200);
+        let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        let (bettor, msg) = place_bet("user3", Side::Up, 200);
+        let err = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::PoolCapReached { denom, cap } if denom == DENOM1 && cap.u128() == 1000
+        ));
+
+        // switching to the crowded side is limited as well
+        let msg = ExecuteMsg::SwitchBetSide {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+        let err = execute(deps.as_mut(), env, mock_info("user2", &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::SideImbalanceTooHigh {}));
+    }
+
+    #[test]
+    fn test_execute_update_fee() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::UpdateFee { fee_bps: 30_000 };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::FeeTooHigh { max_fee_bps: _ }));
+
+        let msg = ExecuteMsg::UpdateFee { fee_bps: 500 };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ANYONE, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(res.attributes, vec![attr("action", "update fee")])
+    }
+
+    #[test]
+    fn test_update_fee_does_not_change_existing_rounds() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let current_time = SystemTime::now();
+        let unix_timestamp = current_time
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get UNIX timestamp")
+            .as_secs();
+
+        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
+        let new_timestamp = unix_timestamp + six_minutes.as_secs();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: new_timestamp,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::UpdateFee { fee_bps: 500 };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: new_timestamp,
+            name: "Round2".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = QueryMsg::GetRound {
+            round_name: "Round1".to_string(),
+        };
+        let res: RoundResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.round.unwrap().fee_bps, DEFAULT_FEE_BPS);
+
+        let msg = QueryMsg::GetRound {
+            round_name: "Round2".to_string(),
+        };
+        let res: RoundResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(res.round.unwrap().fee_bps, 500);
+    }
+
+    #[test]
+    fn test_execute_create_round() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        let current_time = SystemTime::now();
+        let unix_timestamp = current_time
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get UNIX timestamp")
+            .as_secs();
+
+        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
+        let new_timestamp = unix_timestamp + six_minutes.as_secs();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: new_timestamp,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(
+            res.attributes,
+            vec![attr("action", "Create round"), attr("round_id", "1")]
+        )
+    }
+
+    #[test]
+    fn test_execute_create_round_with_duration() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: Some(3_600),
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = QueryMsg::GetRound {
+            round_name: "Round1".to_string(),
+        };
+        let res: RoundResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.round.unwrap().stop_time, start_time + 3_600);
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time,
+            name: "Round2".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: Some(10),
+            open_time: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidRoundDuration { min: 60, max: _ }
+        ));
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: start_time - 1,
+            name: "Round2".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidStartTime { message: _ }
+        ));
+    }
+
+    #[test]
+    fn test_scheduled_round_opens_at_open_time() {
+        let mut deps = mock_dependencies_kujira();
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let open_time = env.block.time.seconds() + 100;
+
+        // the lead time is counted from the open time
+        let msg = ExecuteMsg::CreateRound {
+            start_time: open_time + 200,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: Some(open_time),
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidStartTime { .. }));
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: open_time + 300,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: Some(open_time),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let get_round = QueryMsg::GetRound {
+            round_name: "Round1".to_string(),
+        };
+        let res: RoundResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), get_round.clone()).unwrap()).unwrap();
+        assert_eq!(res.status, Some(RoundStatus::Scheduled));
+
+        let place_bet = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+        let bettor = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            bettor.clone(),
+            place_bet.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::RoundNotOpen {}));
+
+        // admins can start rounds early but not before they open
+        let start_round = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            start_round.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::RoundNotOpen {}));
+
+        env.block.time = Timestamp::from_seconds(open_time);
+        let res: RoundResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), get_round.clone()).unwrap()).unwrap();
+        assert_eq!(res.status, Some(RoundStatus::Open));
+
+        let _res = execute(deps.as_mut(), env.clone(), bettor, place_bet).unwrap();
+        let res: RoundResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), get_round.clone()).unwrap()).unwrap();
+        let round = res.round.unwrap();
+        assert_eq!(round.status, RoundStatus::Open);
+        assert_eq!(round.status_updated_at, open_time);
+
+        let _res = execute(deps.as_mut(), env.clone(), info, start_round).unwrap();
+        let res: RoundResponse =
+            from_binary(&query(deps.as_ref(), env, get_round).unwrap()).unwrap();
+        assert_eq!(res.status, Some(RoundStatus::Locked));
+    }
+
+    #[test]
+    fn test_execute_update_round_timings() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::UpdateRoundTimings {
+            min_round_duration: 600,
+            max_round_duration: 60,
+            min_lead_time: 0,
+            start_grace_period: 300,
+            stop_grace_period: 300,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidRoundTimings { message: _ }
+        ));
+
+        let msg = ExecuteMsg::UpdateRoundTimings {
+            min_round_duration: 60,
+            max_round_duration: 600,
+            min_lead_time: 0,
+            start_grace_period: 300,
+            stop_grace_period: 300,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(res.attributes, vec![attr("action", "update round timings")]);
+
+        // rounds can now start right away
+        let msg = ExecuteMsg::CreateRound {
+            start_time: env.block.time.seconds(),
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: Some(60),
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // a lead time too long for any start time rejects new rounds instead of overflowing
+        let msg = ExecuteMsg::UpdateRoundTimings {
+            min_round_duration: 60,
+            max_round_duration: 600,
+            min_lead_time: u64::MAX,
+            start_grace_period: 300,
+            stop_grace_period: 300,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::CreateRound {
+            start_time: env.block.time.seconds() + 300,
+            name: "Round2".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: Some(60),
+            open_time: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidStartTime { .. }));
+    }
+
+    #[test]
+    fn test_rounds_for_different_assets() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time,
+            name: "Round1".to_string(),
+            asset_denom: "RANDOMASSET".to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::AssetNotSupported {}));
+
+        for (name, asset_denom) in [("Round1", ASSETDENOM), ("Round2", ASSETDENOM2)] {
+            let msg = ExecuteMsg::CreateRound {
+                start_time,
+                name: name.to_string(),
+                asset_denom: asset_denom.to_string(),
+                duration: None,
+                open_time: None,
+            };
+            let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        let mut querier: MockQuerier<KujiraQuery> = MockQuerier::new(&[]);
+        // each asset has its own price
+        querier = querier.with_custom_handler(|query: &KujiraQuery| match query {
+            KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom }) => {
+                let rate = if denom == ASSETDENOM2 { "2.5" } else { "1.23" };
+                let exchange_rate_response = ExchangeRateResponse {
+                    rate: Decimal::from_str(rate).unwrap(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&exchange_rate_response).unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+        deps.querier = querier;
+
+        for name in ["Round1", "Round2"] {
+            let msg = ExecuteMsg::StartRound {
+                name: name.to_string(),
+            };
+            let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        let msg = QueryMsg::GetRound {
+            round_name: "Round1".to_string(),
+        };
+        let res: RoundResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.round.unwrap().start_price,
+            Some(Decimal::from_str("1.23").unwrap())
+        );
+
+        let msg = QueryMsg::GetRound {
+            round_name: "Round2".to_string(),
+        };
+        let res: RoundResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(
+            res.round.unwrap().start_price,
+            Some(Decimal::from_str("2.5").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_round_series_opens_next_round() {
+        let mut deps = mock_dependencies_kujira();
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+        let create_series = |interval: u64| ExecuteMsg::CreateRoundSeries {
+            name: "Series1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            start_time,
+            interval,
+            duration: None,
+            bet_denoms: vec![DENOM1.to_string()],
+        };
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ANYONE, &[]),
+            create_series(600),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        // the next round would start before this one is stopped
+        let err =
+            execute(deps.as_mut(), env.clone(), info.clone(), create_series(300)).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidRoundTimings { .. }));
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), create_series(600)).unwrap();
+        assert_eq!(res.attributes[1], attr("round_name", "Series1-1"));
+
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Series1-1".to_string(),
+        };
+        let bettor = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM2.to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        );
+        let err = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap_err();
+        assert!(matches!(err, ContractError::DenomNotSupported {}));
+
+        env.block.time = Timestamp::from_seconds(start_time);
+        let msg = ExecuteMsg::StartRound {
+            name: "Series1-1".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap();
+
+        env.block.time = Timestamp::from_seconds(start_time + 300);
+        let msg = ExecuteMsg::StopRound {
+            name: "Series1-1".to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap();
+        assert_eq!(res.attributes[1], attr("next_round", "Series1-2"));
+
+        let msg = QueryMsg::GetRound {
+            round_name: "Series1-2".to_string(),
+        };
+        let res: RoundResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        let round = res.round.unwrap();
+        assert_eq!(res.status, Some(RoundStatus::Open));
+        assert_eq!(round.start_time, start_time + 600);
+        assert_eq!(round.stop_time, start_time + 900);
+        assert_eq!(round.series, Some("Series1".to_string()));
+
+        let msg = ExecuteMsg::EndRoundSeries {
+            name: "Series1".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        env.block.time = Timestamp::from_seconds(start_time + 600);
+        let msg = ExecuteMsg::StartRound {
+            name: "Series1-2".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap();
+
+        env.block.time = Timestamp::from_seconds(start_time + 900);
+        let msg = ExecuteMsg::StopRound {
+            name: "Series1-2".to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap();
+        assert_eq!(res.attributes, vec![attr("action", "Stop round")]);
+
+        let msg = QueryMsg::GetRoundSeries {
+            name: "Series1".to_string(),
+        };
+        let res: RoundSeriesResponse =
+            from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        let series = res.series.unwrap();
+        assert!(!series.active);
+        assert_eq!(series.latest_sequence, 2);
+        assert_eq!(series.latest_round, "Series1-2");
+    }
+
+    #[test]
+    fn test_round_series_with_long_lead_time() {
+        let mut deps = mock_dependencies_kujira();
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+        let msg = ExecuteMsg::CreateRoundSeries {
+            name: "Series1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            start_time,
+            interval: 600,
+            duration: None,
+            bet_denoms: vec![DENOM1.to_string()],
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        env.block.time = Timestamp::from_seconds(start_time);
+        let msg = ExecuteMsg::StartRound {
+            name: "Series1-1".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::UpdateRoundTimings {
+            min_round_duration: 60,
+            max_round_duration: 600,
+            min_lead_time: u64::MAX,
+            start_grace_period: 300,
+            stop_grace_period: 300,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        // the next round is pushed to the end of time instead of overflowing
+        env.block.time = Timestamp::from_seconds(start_time + 300);
+        let msg = ExecuteMsg::StopRound {
+            name: "Series1-1".to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        assert_eq!(res.attributes[1], attr("next_round", "Series1-2"));
+        let msg = QueryMsg::GetRound {
+            round_name: "Series1-2".to_string(),
+        };
+        let res: RoundResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(res.round.unwrap().start_time, u64::MAX);
+    }
+
+    #[test]
+    fn test_execute_place_bet_with_accepted_denom() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        let current_time = SystemTime::now();
+        let unix_timestamp = current_time
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get UNIX timestamp")
+            .as_secs();
+
+        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
+        let new_timestamp = unix_timestamp + six_minutes.as_secs();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: new_timestamp,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+
+        let info = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(res.attributes, vec![attr("action", "place bet")])
+    }
+
+    #[test]
+    fn test_execute_place_bet_with_unaccepted_denom() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        let current_time = SystemTime::now();
+        let unix_timestamp = current_time
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get UNIX timestamp")
+            .as_secs();
+
+        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
+        let new_timestamp = unix_timestamp + six_minutes.as_secs();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: new_timestamp,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+
+        let info = mock_info(
+            USER1,
+            &[Coin {
+                denom: "RANDOMDENOM".to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+
+        assert!(matches!(err, ContractError::DenomNotSupported {}))
+    }
+
+    #[test]
+    fn test_execute_withdraw_bet() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        let current_time = SystemTime::now();
+        let unix_timestamp = current_time
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get UNIX timestamp")
+            .as_secs();
+
+        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
+        let new_timestamp = unix_timestamp + six_minutes.as_secs();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: new_timestamp,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+
+        let info = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::WithdrawBet {
+            round_name: "Round1".to_string(),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(res.attributes, vec![attr("action", "withdraw bet")])
+    }
+
+    #[test]
+    fn test_execute_increase_and_switch_bet() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let mut msg = default_instantiate_msg();
+        msg.side_switch_penalty_bps = Some(500);
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: env.block.time.seconds() + 300,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let bet = |side: Side, denom: &str, amount: u128| {
+            (
+                ExecuteMsg::PlaceBet {
+                    side,
+                    round_name: "Round1".to_string(),
+                },
+                mock_info(
+                    USER1,
+                    &[Coin {
+                        denom: denom.to_string(),
+                        amount: Uint128::from(amount),
+                    }],
+                ),
+            )
+        };
+        let (msg, info) = bet(Side::Up, DENOM1, 1000);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let (msg, info) = bet(Side::Up, DENOM1, 500);
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        assert_eq!(res.attributes, vec![attr("action", "increase bet")]);
+
+        let (msg, info) = bet(Side::Up, DENOM2, 500);
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let (msg, info) = bet(Side::Down, DENOM1, 500);
+        let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::BetAlreadyPlaced {}));
+
+        let round = rounds().load(&deps.storage, 1).unwrap();
+        assert_eq!(round.participants_count, 1);
+        assert_eq!(round.up_bets_count, 1);
+        assert_eq!(
+            round.total_up_bet_amount,
+            NativeBalance(vec![
+                Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(1500u128),
+                },
+                Coin {
+                    denom: DENOM2.to_string(),
+                    amount: Uint128::from(500u128),
+                }
+            ])
+        );
+
+        let switch = |side: Side| ExecuteMsg::SwitchBetSide {
+            side,
+            round_name: "Round1".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER1, &[]),
+            switch(Side::Up),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::DuplicateBetSide {}));
+
+        // 5% of the bet is taken in every denom when switching sides
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER1, &[]),
+            switch(Side::Down),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[1], attr("penalty", "denom175denom225"));
+
+        let down = NativeBalance(vec![
+            Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1425u128),
+            },
+            Coin {
+                denom: DENOM2.to_string(),
+                amount: Uint128::from(475u128),
+            },
+        ]);
+        let bet = bets()
+            .load(&deps.storage, (1, Addr::unchecked(USER1)))
+            .unwrap();
+        assert_eq!(bet.side, Side::Down);
+        assert_eq!(bet.amount, down);
+
+        let round = rounds().load(&deps.storage, 1).unwrap();
+        assert_eq!(round.up_bets_count, 0);
+        assert_eq!(round.down_bets_count, 1);
+        assert_eq!(round.total_up_bet_amount, NativeBalance(vec![]));
+        assert_eq!(round.total_down_bet_amount, down);
+        assert_eq!(round.total_bet_amount, down);
+
+        let msg = QueryMsg::GetTreasuryBalance {};
+        let res: TreasuryBalanceResponse =
+            from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(
+            res.treasury_balance.unwrap().balance,
+            NativeBalance(vec![
+                Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(75u128),
+                },
+                Coin {
+                    denom: DENOM2.to_string(),
+                    amount: Uint128::from(25u128),
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_execute_start_round_as_admin() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        let current_time = SystemTime::now();
+        let unix_timestamp = current_time
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get UNIX timestamp")
+            .as_secs();
+
+        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
+        let new_timestamp = unix_timestamp + six_minutes.as_secs();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: new_timestamp,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+
+        let info = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+
+        let info = mock_info(ADMIN1, &[]);
+
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        assert_eq!(res.attributes, vec![attr("action", "Start round")])
+    }
+
+    #[test]
+    fn test_execute_start_round_not_admin() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        let current_time = SystemTime::now();
+        let unix_timestamp = current_time
+            .duration_since(UNIX_EPOCH)
+            .expect("Failed to get UNIX timestamp")
+            .as_secs();
+
+        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
+        let new_timestamp = unix_timestamp + six_minutes.as_secs();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: new_timestamp,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+
+        let info = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+
+        assert!(matches!(err, ContractError::RoundNotReadyToStart {}))
+    }
+
+    #[test]
+    fn test_keepers_start_and_stop_round() {
+        let mut deps = mock_dependencies_kujira();
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let mut msg = default_instantiate_msg();
+        msg.keeper_reward_bps = Some(1_000);
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+        let info = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(500u128),
+            }],
+        );
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Down,
+            round_name: "Round1".to_string(),
+        };
+        let info = mock_info(
+            ANYONE,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(500u128),
+            }],
+        );
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    pub fn mock_dependencies_kujira() -> OwnedDepsType {
-        let querier = MockQuerier::new(&[]).with_custom_handler(|query| match query {
-            // KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom }) => {
-            //     let price = PRICES.with(|p| *p.borrow().get(denom.as_str()).unwrap());
-            //     SystemResult::Ok(ContractResult::Ok(
-            //         to_binary(&ExchangeRateResponse { rate: price }).unwrap(),
-            //     ))
-            // }
+        env.block.time = Timestamp::from_seconds(start_time);
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info(KEEPER1, &[]), msg).unwrap();
+
+        let mut querier: MockQuerier<KujiraQuery> = MockQuerier::new(&[]);
+        // update querier to have price change
+        querier = querier.with_custom_handler(|query: &KujiraQuery| match query {
             KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
                 let exchange_rate_response = ExchangeRateResponse {
-                    rate: Decimal::from_str("1.23").unwrap(),
+                    rate: Decimal::from_str("1.10").unwrap(),
                 };
                 SystemResult::Ok(ContractResult::Ok(
                     to_binary(&exchange_rate_response).unwrap(),
                 ))
             }
-            _ => panic!("Unexpected query: {query:?}"),
+            _ => unimplemented!(),
         });
+        deps.querier = querier;
 
-        OwnedDeps {
-            storage: MockStorage::default(),
-            api: MockApi::default(),
-            querier,
-            custom_query_type: PhantomData,
-        }
+        let msg = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER2, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::RoundStillInProgress {}));
+
+        env.block.time = Timestamp::from_seconds(start_time + 300);
+        let res = execute(deps.as_mut(), env.clone(), mock_info(KEEPER2, &[]), msg).unwrap();
+
+        // each keeper gets 10% of the 150 fee
+        let reward = vec![Coin {
+            denom: DENOM1.to_string(),
+            amount: Uint128::from(15u128),
+        }];
+        assert_eq!(
+            res.messages
+                .into_iter()
+                .map(|m| m.msg)
+                .collect::<Vec<CosmosMsg>>(),
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: KEEPER1.to_string(),
+                    amount: reward.clone(),
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: KEEPER2.to_string(),
+                    amount: reward,
+                }),
+            ]
+        );
+
+        let msg = QueryMsg::GetTreasuryBalance {};
+        let res: TreasuryBalanceResponse =
+            from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(
+            res.treasury_balance.unwrap().balance,
+            NativeBalance(vec![Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(120u128),
+            }])
+        );
     }
 
     #[test]
-    fn test_instantiate() {
+    fn test_claim_refund_from_round_never_started() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time,
+            name: "Round1".to_string(),
             asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
+            duration: Some(3_600),
+            open_time: None,
         };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        let res = instantiate(deps.as_mut(), env, info, msg).unwrap();
-        assert_eq!(res.attributes, vec![attr("action", "instantiate")])
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+        let user_info = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+        let _res = execute(deps.as_mut(), mock_env(), user_info, msg).unwrap();
+
+        let msg = ExecuteMsg::ClaimRefund {
+            round_name: "Round1".to_string(),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::RoundNotCancelled {}));
+
+        // the start grace period is over
+        env.block.time = Timestamp::from_seconds(start_time + 301);
+
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+        let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::RoundStartWindowPassed {}));
+
+        let msg = ExecuteMsg::ClaimRefund {
+            round_name: "Round1".to_string(),
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER1, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: USER1.to_string(),
+                amount: vec![Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(1000u128),
+                }],
+            })
+        );
+
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::RefundAlreadyClaimed {}));
+
+        let msg = QueryMsg::GetRound {
+            round_name: "Round1".to_string(),
+        };
+        let res: RoundResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(res.round.unwrap().status, RoundStatus::Cancelled);
     }
 
     #[test]
-    fn test_execute_update_admins() {
+    fn test_cancel_round_never_stopped() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time,
+            name: "Round1".to_string(),
             asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
+            duration: None,
+            open_time: None,
         };
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+        env.block.time = Timestamp::from_seconds(start_time);
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        //
-        let msg = ExecuteMsg::UpdateAdmins {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string(), ANYONE.to_string()],
+        let msg = ExecuteMsg::CancelRound {
+            name: "Round1".to_string(),
         };
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::RoundNotCancelled {}));
+
+        // lowering the grace periods does not affect rounds that were already created
+        let msg = ExecuteMsg::UpdateRoundTimings {
+            min_round_duration: 60,
+            max_round_duration: 3600,
+            min_lead_time: 300,
+            start_grace_period: 10,
+            stop_grace_period: 10,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::CancelRound {
+            name: "Round1".to_string(),
+        };
+        env.block.time = Timestamp::from_seconds(start_time + 300 + 300);
+        let err = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::RoundNotCancelled {}));
 
-        assert_eq!(res.attributes, vec![attr("action", "update admins")])
+        // the stop grace period is over
+        env.block.time = Timestamp::from_seconds(start_time + 300 + 301);
+
+        let msg = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+        let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::RoundStopWindowPassed {}));
+
+        let msg = ExecuteMsg::CancelRound {
+            name: "Round1".to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap();
+        assert_eq!(res.attributes, vec![attr("action", "Cancel round")]);
+
+        let msg = ExecuteMsg::ClaimWin {
+            round_name: "Round1".to_string(),
+        };
+        let err = execute(deps.as_mut(), env, mock_info(USER1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::RoundCancelled {}));
     }
 
     #[test]
-    fn test_execute_update_asset_denom() {
+    fn test_round_far_in_the_future() {
         let mut deps = mock_dependencies_kujira();
         let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ANYONE, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
-        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let _res = instantiate(deps.as_mut(), env.clone(), mock_info(ADMIN1, &[]), msg).unwrap();
 
-        let msg = ExecuteMsg::UpdateAssetDenom {
-            asset_denom: ASSETDENOM2.to_string(),
+        let msg = ExecuteMsg::CreateRound {
+            start_time: u64::MAX - 10,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: Some(60),
+            open_time: None,
         };
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidStartTime { .. }));
 
-        assert_eq!(res.attributes, vec![attr("action", "update asset denom")])
+        // the grace periods can't overflow past the end of time either
+        let msg = ExecuteMsg::CreateRound {
+            start_time: u64::MAX - 100,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: Some(60),
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let msg = QueryMsg::GetRounds {
+            start_after: None,
+            limit: None,
+            order: None,
+            filter: Some(RoundFilter {
+                status: Some(RoundStatus::Open),
+                asset_denom: None,
+                creator: None,
+            }),
+        };
+        let res: AllRoundsResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!(res.rounds.len(), 1);
     }
 
     #[test]
-    fn test_execute_update_accepted_bet_denom() {
+    fn test_oracle_guards() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
+        let mut msg = default_instantiate_msg();
+        msg.max_price_deviation_bps = Some(500);
+        msg.oracle_grace_period = Some(60);
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+        let stop_time = start_time + 300;
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time,
+            name: "Round1".to_string(),
             asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
+            duration: None,
+            open_time: None,
         };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+        let bettor = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+        let _res = execute(deps.as_mut(), mock_env(), bettor, msg).unwrap();
+
+        let price_querier = |rate: &'static str| {
+            MockQuerier::new(&[]).with_custom_handler(move |query: &KujiraQuery| match query {
+                KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
+                    let exchange_rate_response = ExchangeRateResponse {
+                        rate: Decimal::from_str(rate).unwrap(),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&exchange_rate_response).unwrap(),
+                    ))
+                }
+                _ => unimplemented!(),
+            })
+        };
 
-        let msg = ExecuteMsg::UpdateAcceptedBetDenoms {
-            accepted_bet_denoms: vec![
-                String::from(DENOM1),
-                String::from(DENOM2),
-                String::from(DENOM3),
-            ],
+        env.block.time = Timestamp::from_seconds(start_time);
+        deps.querier = price_querier("0");
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
         };
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER1, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ZeroOraclePrice { .. }));
+        deps.querier = price_querier("1.23");
+        let _res = execute(deps.as_mut(), env.clone(), mock_info(KEEPER1, &[]), msg).unwrap();
+
+        // a move of more than 5% is rejected
+        env.block.time = Timestamp::from_seconds(stop_time);
+        deps.querier = price_querier("1.10");
+        let msg = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER2, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::PriceDeviationTooHigh { .. }));
 
-        assert_eq!(
-            res.attributes,
-            vec![attr("action", "update accepted bet denoms")]
+        deps.querier = MockQuerier::new(&[]).with_custom_handler(|_| {
+            SystemResult::Ok(ContractResult::Err("oracle unavailable".to_string()))
+        });
+        env.block.time = Timestamp::from_seconds(stop_time + 60);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER2, &[]),
+            msg.clone(),
         )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::OracleUnavailable { .. }));
+
+        // once the oracle grace period is over the round is cancelled and bets can be refunded
+        env.block.time = Timestamp::from_seconds(stop_time + 61);
+        let res = execute(deps.as_mut(), env.clone(), mock_info(KEEPER2, &[]), msg).unwrap();
+        assert_eq!(res.attributes[0], attr("action", "Cancel round"));
+        assert_eq!(res.attributes[1].key, "reason");
+        let round = rounds().load(&deps.storage, 1).unwrap();
+        assert_eq!(round.status, RoundStatus::Cancelled);
+
+        let msg = ExecuteMsg::ClaimRefund {
+            round_name: "Round1".to_string(),
+        };
+        let res = execute(deps.as_mut(), env, mock_info(USER1, &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
     }
 
     #[test]
-    fn test_execute_create_round() {
+    fn test_price_sources() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let mut msg = default_instantiate_msg();
+        msg.price_sources = Some(vec![
+            AssetPriceSource {
+                denom: ASSETDENOM.to_string(),
+                source: PriceSourceMsg::Reporter {
+                    reporter: REPORTER.to_string(),
+                    max_age: 60,
+                },
+            },
+            AssetPriceSource {
+                denom: ASSETDENOM2.to_string(),
+                source: PriceSourceMsg::Fin {
+                    pair: FINPAIR.to_string(),
+                },
+            },
+        ]);
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == FINPAIR => {
+                let pool = |price: &str| PoolResponse {
+                    quote_price: Decimal256::from_str(price).unwrap(),
+                    offer_denom: Denom::Native(ASSETDENOM2.to_string()),
+                    total_offer_amount: Uint256::from(1000u128),
+                };
+                let book = BookResponse {
+                    base: vec![pool("2.0")],
+                    quote: vec![pool("1.8")],
+                };
+                SystemResult::Ok(ContractResult::Ok(to_binary(&book).unwrap()))
+            }
+            _ => panic!("Unexpected query: {query:?}"),
+        });
+
+        let start_time = env.block.time.seconds() + 300;
+        let stop_time = start_time + 300;
+        for (name, asset_denom) in [("Round1", ASSETDENOM), ("Round2", ASSETDENOM2)] {
+            let msg = ExecuteMsg::CreateRound {
+                start_time,
+                name: name.to_string(),
+                asset_denom: asset_denom.to_string(),
+                duration: None,
+                open_time: None,
+            };
+            let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        env.block.time = Timestamp::from_seconds(start_time);
+        let start = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
         };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), start.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::OracleUnavailable { .. }));
 
-        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let report = |price: &str| ExecuteMsg::ReportPrice {
+            denom: ASSETDENOM.to_string(),
+            price: Decimal::from_str(price).unwrap(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ANYONE, &[]),
+            report("1.5"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let reporter = mock_info(REPORTER, &[]);
+        let _res = execute(deps.as_mut(), env.clone(), reporter.clone(), report("1.5")).unwrap();
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), start).unwrap();
+        let round = rounds().load(&deps.storage, 1).unwrap();
+        assert_eq!(round.start_price, Some(Decimal::from_str("1.5").unwrap()));
+
+        // FIN rounds start at the mid price of the book
+        let start = ExecuteMsg::StartRound {
+            name: "Round2".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), start).unwrap();
+        let round = rounds().load(&deps.storage, 2).unwrap();
+        assert_eq!(round.start_price, Some(Decimal::from_str("1.9").unwrap()));
+
+        // a reported price older than its max age is not used
+        env.block.time = Timestamp::from_seconds(stop_time);
+        let stop = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), stop.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::OracleUnavailable { .. }));
+        let _res = execute(deps.as_mut(), env.clone(), reporter, report("1.6")).unwrap();
+        let _res = execute(deps.as_mut(), env, info, stop).unwrap();
+        let round = rounds().load(&deps.storage, 1).unwrap();
+        assert_eq!(round.stop_price, Some(Decimal::from_str("1.6").unwrap()));
+    }
+
+    #[test]
+    fn test_execute_stop_round_while_in_progress() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         let current_time = SystemTime::now();
         let unix_timestamp = current_time
@@ -735,25 +4173,53 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+
+        let info = mock_info(
+            USER1,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+
+        let info = mock_info(ADMIN1, &[]);
+
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+
+        let err = execute(deps.as_mut(), env, info.clone(), msg).unwrap_err();
 
-        assert_eq!(res.attributes, vec![attr("action", "Create round")])
+        assert!(matches!(err, ContractError::RoundStillInProgress {}))
     }
 
     #[test]
-    fn test_execute_place_bet_with_accepted_denom() {
+    fn test_execute_stop_round() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
-        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         let current_time = SystemTime::now();
         let unix_timestamp = current_time
@@ -767,6 +4233,9 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -777,30 +4246,44 @@ mod tests {
 
         let info = mock_info(
             USER1,
-            &vec![Coin {
+            &[Coin {
                 denom: DENOM1.to_string(),
                 amount: Uint128::from(1000u128),
             }],
         );
 
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        assert_eq!(res.attributes, vec![attr("action", "place bet")])
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+
+        let info = mock_info(ADMIN1, &[]);
+
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+
+        let twelve_minutes = Duration::from_secs(12 * 60); // 6 minutes in seconds
+        let stop_timestamp = unix_timestamp + twelve_minutes.as_secs();
+        env.block.time = Timestamp::from_seconds(stop_timestamp);
+
+        let res = execute(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        assert_eq!(res.attributes, vec![attr("action", "Stop round")])
     }
 
     #[test]
-    fn test_execute_place_bet_with_unaccepted_denom() {
+    fn test_execute_claim_win_of_existing_bet() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
-        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         let current_time = SystemTime::now();
         let unix_timestamp = current_time
@@ -814,6 +4297,9 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -824,30 +4310,51 @@ mod tests {
 
         let info = mock_info(
             USER1,
-            &vec![Coin {
-                denom: "RANDOMDENOM".to_string(),
+            &[Coin {
+                denom: DENOM1.to_string(),
                 amount: Uint128::from(1000u128),
             }],
         );
 
-        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        assert!(matches!(err, ContractError::DenomNotSupported {}))
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+
+        let info = mock_info(ADMIN1, &[]);
+
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+
+        let twelve_minutes = Duration::from_secs(12 * 60); // 6 minutes in seconds
+        let stop_timestamp = unix_timestamp + twelve_minutes.as_secs();
+        env.block.time = Timestamp::from_seconds(stop_timestamp);
+
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::ClaimWin {
+            round_name: "Round1".to_string(),
+        };
+
+        let info = mock_info(USER1, &[]);
+        let res = execute(deps.as_mut(), env, info.clone(), msg).unwrap();
+
+        assert_eq!(res.attributes, vec![attr("action", "claim win")])
     }
 
     #[test]
-    fn test_execute_withdraw_bet() {
+    fn test_execute_claim_win_of_nonexisting_bet() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
-        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         let current_time = SystemTime::now();
         let unix_timestamp = current_time
@@ -861,6 +4368,9 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -871,7 +4381,7 @@ mod tests {
 
         let info = mock_info(
             USER1,
-            &vec![Coin {
+            &[Coin {
                 denom: DENOM1.to_string(),
                 amount: Uint128::from(1000u128),
             }],
@@ -879,28 +4389,45 @@ mod tests {
 
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        let msg = ExecuteMsg::WithdrawBet {
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+
+        let info = mock_info(ADMIN1, &[]);
+
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+
+        let twelve_minutes = Duration::from_secs(12 * 60); // 6 minutes in seconds
+        let stop_timestamp = unix_timestamp + twelve_minutes.as_secs();
+        env.block.time = Timestamp::from_seconds(stop_timestamp);
+
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::ClaimWin {
             round_name: "Round1".to_string(),
         };
 
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let err = execute(deps.as_mut(), env, info.clone(), msg).unwrap_err();
 
-        assert_eq!(res.attributes, vec![attr("action", "withdraw bet")])
+        assert!(matches!(
+            err,
+            ContractError::Std(StdError::NotFound { kind: _ })
+        ))
     }
 
     #[test]
-    fn test_execute_start_round_as_admin() {
+    fn test_execute_claim_win_pays_share_of_losing_pool() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
-        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         let current_time = SystemTime::now();
         let unix_timestamp = current_time
@@ -914,371 +4441,790 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
         let msg = ExecuteMsg::PlaceBet {
-            side: Side::Up,
+            side: Side::Down,
             round_name: "Round1".to_string(),
         };
-
         let info = mock_info(
             USER1,
-            &vec![Coin {
+            &[Coin {
                 denom: DENOM1.to_string(),
                 amount: Uint128::from(1000u128),
             }],
         );
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Up,
+            round_name: "Round1".to_string(),
+        };
+        let info = mock_info(
+            ANYONE,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
         let msg = ExecuteMsg::StartRound {
             name: "Round1".to_string(),
         };
 
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        assert_eq!(res.attributes, vec![attr("action", "Start round")])
+        let mut querier: MockQuerier<KujiraQuery> = MockQuerier::new(&[]);
+        // update querier to have the price go down
+        querier = querier.with_custom_handler(|query: &KujiraQuery| match query {
+            KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
+                let exchange_rate_response = ExchangeRateResponse {
+                    rate: Decimal::from_str("1.10").unwrap(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&exchange_rate_response).unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+        deps.querier = querier;
+
+        let msg = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+
+        let twelve_minutes = Duration::from_secs(12 * 60); // 12 minutes in seconds
+        let stop_timestamp = unix_timestamp + twelve_minutes.as_secs();
+        env.block.time = Timestamp::from_seconds(stop_timestamp);
+
+        let get_claimable = |deps: Deps<KujiraQuery>, user: &str| {
+            let msg = QueryMsg::GetClaimable {
+                round_name: "Round1".to_string(),
+                user_addr: user.to_string(),
+            };
+            let res: ClaimableResponse =
+                from_binary(&query(deps, env.clone(), msg).unwrap()).unwrap();
+            res
+        };
+        // nothing can be claimed while the round is running
+        let res = get_claimable(deps.as_ref(), USER1);
+        assert_eq!(res.round_status, RoundStatus::Locked);
+        assert_eq!(res.kind, None);
+
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let winnings = vec![Coin {
+            denom: DENOM1.to_string(),
+            amount: Uint128::from(1700u128),
+        }];
+        let res = get_claimable(deps.as_ref(), USER1);
+        assert_eq!(res.kind, Some(PayoutKind::Win));
+        assert_eq!(res.coins, winnings);
+        assert!(!res.claimed);
+
+        let res = get_claimable(deps.as_ref(), ANYONE);
+        assert_eq!(res.kind, Some(PayoutKind::Loss));
+        assert_eq!(res.coins, vec![]);
+
+        let msg = ExecuteMsg::ClaimWin {
+            round_name: "Round1".to_string(),
+        };
+
+        let info = mock_info(USER1, &[]);
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: USER1.to_string(),
+                amount: vec![Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(1700u128),
+                }],
+            })
+        );
+
+        let err = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::WinAlreadyClaimed {}));
+
+        let res = get_claimable(deps.as_ref(), USER1);
+        assert_eq!(res.coins, winnings);
+        assert!(res.claimed);
+
+        let info = mock_info(ANYONE, &[]);
+        let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::YouLost {}));
     }
 
     #[test]
-    fn test_execute_start_round_not_admin() {
+    fn test_one_sided_round_is_a_push() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
-
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
 
-        let _res = instantiate(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let msg = default_instantiate_msg();
 
-        let current_time = SystemTime::now();
-        let unix_timestamp = current_time
-            .duration_since(UNIX_EPOCH)
-            .expect("Failed to get UNIX timestamp")
-            .as_secs();
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
-        let new_timestamp = unix_timestamp + six_minutes.as_secs();
+        let start_time = env.block.time.seconds() + 300;
 
         let msg = ExecuteMsg::CreateRound {
-            start_time: new_timestamp,
+            start_time,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
         let msg = ExecuteMsg::PlaceBet {
-            side: Side::Up,
+            side: Side::Down,
             round_name: "Round1".to_string(),
         };
-
         let info = mock_info(
             USER1,
-            &vec![Coin {
+            &[Coin {
                 denom: DENOM1.to_string(),
                 amount: Uint128::from(1000u128),
             }],
         );
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
+        env.block.time = Timestamp::from_seconds(start_time);
         let msg = ExecuteMsg::StartRound {
             name: "Round1".to_string(),
         };
+        let _res = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap();
 
-        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        let mut querier: MockQuerier<KujiraQuery> = MockQuerier::new(&[]);
+        // update querier to have the price go down
+        querier = querier.with_custom_handler(|query: &KujiraQuery| match query {
+            KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
+                let exchange_rate_response = ExchangeRateResponse {
+                    rate: Decimal::from_str("1.10").unwrap(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&exchange_rate_response).unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+        deps.querier = querier;
+
+        env.block.time = Timestamp::from_seconds(start_time + 300);
+        let msg = ExecuteMsg::StopRound {
+            name: "Round1".to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap();
+
+        // nobody bet against USER1 so no fee is taken
+        assert!(res.messages.is_empty());
+        let msg = QueryMsg::GetTreasuryBalance {};
+        let res: TreasuryBalanceResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.treasury_balance,
+            Some(TreasuryBalance {
+                balance: NativeBalance(vec![]),
+            })
+        );
+
+        let msg = QueryMsg::GetRound {
+            round_name: "Round1".to_string(),
+        };
+        let res: RoundResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.round.unwrap().outcome, Some(RoundOutcome::Push));
 
-        assert!(matches!(err, ContractError::Unauthorized {}))
+        let msg = ExecuteMsg::ClaimWin {
+            round_name: "Round1".to_string(),
+        };
+        let res = execute(deps.as_mut(), env, mock_info(USER1, &[]), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: USER1.to_string(),
+                amount: vec![Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(1000u128),
+                }],
+            })
+        );
     }
 
     #[test]
-    fn test_execute_stop_round_while_in_progress() {
+    fn test_claim_wins_and_claim_all() {
         let mut deps = mock_dependencies_kujira();
-        let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let current_time = SystemTime::now();
-        let unix_timestamp = current_time
-            .duration_since(UNIX_EPOCH)
-            .expect("Failed to get UNIX timestamp")
-            .as_secs();
+        let start_time = env.block.time.seconds() + 300;
+        for name in ["Round1", "Round2", "Round3"] {
+            let msg = ExecuteMsg::CreateRound {
+                start_time,
+                name: name.to_string(),
+                asset_denom: ASSETDENOM.to_string(),
+                duration: None,
+                open_time: None,
+            };
+            let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
 
-        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
-        let new_timestamp = unix_timestamp + six_minutes.as_secs();
+        let round_bets = [
+            (USER1, "Round1", Side::Up),
+            (USER1, "Round2", Side::Down),
+            (ANYONE, "Round2", Side::Up),
+            (USER1, "Round3", Side::Up),
+            (ANYONE, "Round3", Side::Down),
+        ];
+        for (user, round_name, side) in round_bets {
+            let msg = ExecuteMsg::PlaceBet {
+                side,
+                round_name: round_name.to_string(),
+            };
+            let bettor = mock_info(
+                user,
+                &[Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+            );
+            let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        }
+
+        for name in ["Round2", "Round3"] {
+            let msg = ExecuteMsg::StartRound {
+                name: name.to_string(),
+            };
+            let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let mut querier: MockQuerier<KujiraQuery> = MockQuerier::new(&[]);
+        // update querier to have the price go down
+        querier = querier.with_custom_handler(|query: &KujiraQuery| match query {
+            KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
+                let exchange_rate_response = ExchangeRateResponse {
+                    rate: Decimal::from_str("1.10").unwrap(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&exchange_rate_response).unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+        deps.querier = querier;
+
+        // Round1 was never started so it can be refunded
+        env.block.time = Timestamp::from_seconds(start_time + 301);
+        for name in ["Round2", "Round3"] {
+            let msg = ExecuteMsg::StopRound {
+                name: name.to_string(),
+            };
+            let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
 
-        let msg = ExecuteMsg::CreateRound {
-            start_time: new_timestamp,
-            name: "Round1".to_string(),
+        let send = |user: &str, amount: u128| {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: user.to_string(),
+                amount: vec![Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            })
         };
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        let msg = ExecuteMsg::PlaceBet {
-            side: Side::Up,
-            round_name: "Round1".to_string(),
+        let msg = ExecuteMsg::ClaimWins {
+            round_names: vec!["Round1".to_string()],
         };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(ANYONE, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::BetNotFound {}));
 
-        let info = mock_info(
-            USER1,
-            &vec![Coin {
-                denom: DENOM1.to_string(),
-                amount: Uint128::from(1000u128),
-            }],
-        );
-
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
-        let msg = ExecuteMsg::StartRound {
-            name: "Round1".to_string(),
+        // the lost bet in Round3 is skipped
+        let msg = ExecuteMsg::ClaimWins {
+            round_names: vec!["Round2".to_string(), "Round3".to_string()],
         };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER1, &[]), msg).unwrap();
+        assert_eq!(res.attributes[1], attr("claimed_rounds", "1"));
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, send(USER1, 170));
+        let bet = bets()
+            .load(&deps.storage, (3, Addr::unchecked(USER1)))
+            .unwrap();
+        assert!(bet.lost);
+
+        // only the refund from Round1 is left, bets are scanned a page at a time
+        let msg = ExecuteMsg::ClaimAll {
+            start_after: None,
+            limit: Some(1),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER1, &[]), msg).unwrap();
+        assert_eq!(res.messages[0].msg, send(USER1, 100));
+        assert_eq!(res.attributes[2], attr("next_start_after", "1"));
 
-        let info = mock_info(ADMIN1, &vec![]);
-
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
-        let msg = ExecuteMsg::StopRound {
-            name: "Round1".to_string(),
+        let msg = ExecuteMsg::ClaimAll {
+            start_after: Some(1),
+            limit: Some(2),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER1, &[]), msg).unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(res.attributes[1], attr("claimed_rounds", "0"));
+        assert_eq!(res.attributes[2], attr("next_start_after", "3"));
+
+        let msg = ExecuteMsg::ClaimAll {
+            start_after: None,
+            limit: None,
         };
 
-        let err = execute(deps.as_mut(), env, info.clone(), msg).unwrap_err();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER1, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NothingToClaim {}));
 
-        assert!(matches!(err, ContractError::RoundStillInProgress {}))
+        let res = execute(deps.as_mut(), env, mock_info(ANYONE, &[]), msg).unwrap();
+        assert_eq!(res.messages[0].msg, send(ANYONE, 170));
     }
 
     #[test]
-    fn test_execute_stop_round() {
+    fn test_distribute_round() {
         let mut deps = mock_dependencies_kujira();
         let mut env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let current_time = SystemTime::now();
-        let unix_timestamp = current_time
-            .duration_since(UNIX_EPOCH)
-            .expect("Failed to get UNIX timestamp")
-            .as_secs();
-
-        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
-        let new_timestamp = unix_timestamp + six_minutes.as_secs();
-
+        let start_time = env.block.time.seconds() + 300;
         let msg = ExecuteMsg::CreateRound {
-            start_time: new_timestamp,
+            start_time,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
-        let msg = ExecuteMsg::PlaceBet {
-            side: Side::Up,
-            round_name: "Round1".to_string(),
-        };
-
-        let info = mock_info(
-            USER1,
-            &vec![Coin {
-                denom: DENOM1.to_string(),
-                amount: Uint128::from(1000u128),
-            }],
-        );
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let bets = [
+            (USER1, Side::Down, 100u128),
+            (KEEPER1, Side::Down, 100u128),
+            (ANYONE, Side::Up, 200u128),
+        ];
+        for (user, side, amount) in bets {
+            let msg = ExecuteMsg::PlaceBet {
+                side,
+                round_name: "Round1".to_string(),
+            };
+            let bettor = mock_info(
+                user,
+                &[Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            );
+            let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        }
 
         let msg = ExecuteMsg::StartRound {
             name: "Round1".to_string(),
         };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let info = mock_info(ADMIN1, &vec![]);
+        let distribute = ExecuteMsg::DistributeRound {
+            round_name: "Round1".to_string(),
+            limit: Some(2),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ANYONE, &[]),
+            distribute.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::RoundStillInProgress {}));
 
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let mut querier: MockQuerier<KujiraQuery> = MockQuerier::new(&[]);
+        // update querier to have the price go down
+        querier = querier.with_custom_handler(|query: &KujiraQuery| match query {
+            KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
+                let exchange_rate_response = ExchangeRateResponse {
+                    rate: Decimal::from_str("1.10").unwrap(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&exchange_rate_response).unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+        deps.querier = querier;
 
+        env.block.time = Timestamp::from_seconds(start_time + 300);
         let msg = ExecuteMsg::StopRound {
             name: "Round1".to_string(),
         };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let winnings = |user: &str| {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: user.to_string(),
+                amount: vec![Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(170u128),
+                }],
+            })
+        };
 
-        let twelve_minutes = Duration::from_secs(12 * 60); // 6 minutes in seconds
-        let stop_timestamp = unix_timestamp + twelve_minutes.as_secs();
-        env.block.time = Timestamp::from_seconds(stop_timestamp);
+        // bets are paid in address order, ANYONE lost and gets nothing
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ANYONE, &[]),
+            distribute.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[2], attr("finished", "false"));
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.messages[0].msg, winnings(KEEPER1));
 
-        let res = execute(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::ClaimWin {
+            round_name: "Round1".to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER1, &[]), msg).unwrap();
+        assert_eq!(res.messages[0].msg, winnings(USER1));
+
+        // USER1 already claimed so there is nothing left to pay
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ANYONE, &[]),
+            distribute.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[2], attr("finished", "true"));
+        assert!(res.messages.is_empty());
 
-        assert_eq!(res.attributes, vec![attr("action", "Stop round")])
+        let err = execute(deps.as_mut(), env, mock_info(ANYONE, &[]), distribute).unwrap_err();
+        assert!(matches!(err, ContractError::RoundAlreadyDistributed {}));
     }
 
     #[test]
-    fn test_execute_claim_win_of_existing_bet() {
+    fn test_sweep_expired() {
         let mut deps = mock_dependencies_kujira();
         let mut env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let mut msg = default_instantiate_msg();
+        msg.claim_window = Some(1_000);
 
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let current_time = SystemTime::now();
-        let unix_timestamp = current_time
-            .duration_since(UNIX_EPOCH)
-            .expect("Failed to get UNIX timestamp")
-            .as_secs();
-
-        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
-        let new_timestamp = unix_timestamp + six_minutes.as_secs();
-
+        let start_time = env.block.time.seconds() + 300;
         let msg = ExecuteMsg::CreateRound {
-            start_time: new_timestamp,
+            start_time,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-
-        let msg = ExecuteMsg::PlaceBet {
-            side: Side::Up,
-            round_name: "Round1".to_string(),
-        };
-
-        let info = mock_info(
-            USER1,
-            &vec![Coin {
-                denom: DENOM1.to_string(),
-                amount: Uint128::from(1000u128),
-            }],
-        );
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let bets = [
+            (USER1, Side::Down, 100u128),
+            (KEEPER1, Side::Down, 100u128),
+            (ANYONE, Side::Up, 200u128),
+        ];
+        for (user, side, amount) in bets {
+            let msg = ExecuteMsg::PlaceBet {
+                side,
+                round_name: "Round1".to_string(),
+            };
+            let bettor = mock_info(
+                user,
+                &[Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            );
+            let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        }
 
         let msg = ExecuteMsg::StartRound {
             name: "Round1".to_string(),
         };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let info = mock_info(ADMIN1, &vec![]);
-
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let mut querier: MockQuerier<KujiraQuery> = MockQuerier::new(&[]);
+        // update querier to have the price go down
+        querier = querier.with_custom_handler(|query: &KujiraQuery| match query {
+            KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
+                let exchange_rate_response = ExchangeRateResponse {
+                    rate: Decimal::from_str("1.10").unwrap(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&exchange_rate_response).unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+        deps.querier = querier;
 
+        let stop_time = start_time + 300;
+        env.block.time = Timestamp::from_seconds(stop_time);
         let msg = ExecuteMsg::StopRound {
             name: "Round1".to_string(),
         };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-        let twelve_minutes = Duration::from_secs(12 * 60); // 6 minutes in seconds
-        let stop_timestamp = unix_timestamp + twelve_minutes.as_secs();
-        env.block.time = Timestamp::from_seconds(stop_timestamp);
+        let sweep = ExecuteMsg::SweepExpired {
+            round_name: "Round1".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ANYONE, &[]),
+            sweep.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ClaimWindowNotExpired {}));
 
-        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let claim_win = ExecuteMsg::ClaimWin {
+            round_name: "Round1".to_string(),
+        };
+        let _res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER1, &[]),
+            claim_win.clone(),
+        )
+        .unwrap();
+
+        // KEEPER1 never claimed their 170 before the window closed
+        env.block.time = Timestamp::from_seconds(stop_time + 1_001);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(KEEPER1, &[]),
+            claim_win,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ClaimWindowExpired {}));
 
-        let msg = ExecuteMsg::ClaimWin {
+        let msg = QueryMsg::GetClaimable {
             round_name: "Round1".to_string(),
+            user_addr: KEEPER1.to_string(),
         };
+        let res: ClaimableResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert!(res.claim_window_expired);
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ANYONE, &[]),
+            sweep.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "Sweep expired"),
+                attr("swept_amount", "denom1170")
+            ]
+        );
 
-        let info = mock_info(USER1, &vec![]);
-        let res = execute(deps.as_mut(), env, info.clone(), msg).unwrap();
+        let round = rounds().load(&deps.storage, 1).unwrap();
+        assert_eq!(
+            round.swept_amount,
+            Some(NativeBalance(vec![Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(170u128),
+            }]))
+        );
 
-        assert_eq!(res.attributes, vec![attr("action", "claim win")])
+        // the fee and the unclaimed win both end up in the treasury
+        let msg = QueryMsg::GetTreasuryBalance {};
+        let res: TreasuryBalanceResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(
+            res.treasury_balance.unwrap().balance,
+            NativeBalance(vec![Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(230u128),
+            }])
+        );
+
+        let err = execute(deps.as_mut(), env, mock_info(ANYONE, &[]), sweep).unwrap_err();
+        assert!(matches!(err, ContractError::RoundAlreadySwept {}));
     }
 
     #[test]
-    fn test_execute_claim_win_of_nonexisting_bet() {
+    fn test_cw20_bet_paid_out_with_transfers() {
         let mut deps = mock_dependencies_kujira();
         let mut env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let mut msg = default_instantiate_msg();
+        msg.accepted_cw20_tokens = Some(vec![AcceptedCw20Token {
+            address: CW20TOKEN.to_string(),
+            oracle_denom: DENOM2.to_string(),
+            min_bet: Uint128::from(50u128),
+            max_bet: Some(Uint128::from(100u128)),
+        }]);
 
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let current_time = SystemTime::now();
-        let unix_timestamp = current_time
-            .duration_since(UNIX_EPOCH)
-            .expect("Failed to get UNIX timestamp")
-            .as_secs();
-
-        let six_minutes = Duration::from_secs(6 * 60); // 6 minutes in seconds
-        let new_timestamp = unix_timestamp + six_minutes.as_secs();
-
+        let start_time = env.block.time.seconds() + 300;
         let msg = ExecuteMsg::CreateRound {
-            start_time: new_timestamp,
+            start_time,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let receive = |amount: u128| {
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: USER1.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&ReceiveMsg::PlaceBet {
+                    side: Side::Down,
+                    round_name: "Round1".to_string(),
+                })
+                .unwrap(),
+            })
+        };
+        // cw20 bets are held to the token's bet limits
+        for (amount, too_small) in [(10u128, true), (150u128, false)] {
+            let err = execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(CW20TOKEN, &[]),
+                receive(amount),
+            )
+            .unwrap_err();
+            if too_small {
+                assert!(matches!(err, ContractError::BetTooSmall { .. }));
+            } else {
+                assert!(matches!(err, ContractError::BetTooLarge { .. }));
+            }
+        }
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("othertoken", &[]),
+            receive(100),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::DenomNotSupported {}));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CW20TOKEN, &[]),
+            receive(100),
+        )
+        .unwrap();
+        assert_eq!(res.attributes, vec![attr("action", "place bet")]);
 
         let msg = ExecuteMsg::PlaceBet {
             side: Side::Up,
             round_name: "Round1".to_string(),
         };
-
-        let info = mock_info(
-            USER1,
-            &vec![Coin {
+        let bettor = mock_info(
+            ANYONE,
+            &[Coin {
                 denom: DENOM1.to_string(),
-                amount: Uint128::from(1000u128),
+                amount: Uint128::from(100u128),
             }],
         );
-
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
 
         let msg = ExecuteMsg::StartRound {
             name: "Round1".to_string(),
         };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let info = mock_info(ADMIN1, &vec![]);
-
-        let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let mut querier: MockQuerier<KujiraQuery> = MockQuerier::new(&[]);
+        // update querier to have the price go down
+        querier = querier.with_custom_handler(|query: &KujiraQuery| match query {
+            KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
+                let exchange_rate_response = ExchangeRateResponse {
+                    rate: Decimal::from_str("1.10").unwrap(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&exchange_rate_response).unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+        deps.querier = querier;
 
+        env.block.time = Timestamp::from_seconds(start_time + 300);
         let msg = ExecuteMsg::StopRound {
             name: "Round1".to_string(),
         };
-
-        let twelve_minutes = Duration::from_secs(12 * 60); // 6 minutes in seconds
-        let stop_timestamp = unix_timestamp + twelve_minutes.as_secs();
-        env.block.time = Timestamp::from_seconds(stop_timestamp);
-
         let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
+        let cw20_transfer = |recipient: &str, amount: u128| {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW20TOKEN.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::from(amount),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        };
+
+        // native winnings are sent with the bank and the cw20 stake with a token transfer
         let msg = ExecuteMsg::ClaimWin {
             round_name: "Round1".to_string(),
         };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER1, &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: USER1.to_string(),
+                amount: vec![Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(85u128),
+                }],
+            })
+        );
+        assert_eq!(res.messages[1].msg, cw20_transfer(USER1, 85));
 
-        let err = execute(deps.as_mut(), env, info.clone(), msg).unwrap_err();
-
-        assert!(matches!(
-            err,
-            ContractError::Std(StdError::NotFound { kind: _ })
-        ))
+        let msg = ExecuteMsg::WithdrawFromPool {
+            to_address: TREASURY.to_string(),
+            denom: cw20_denom(CW20TOKEN),
+            amount: 15,
+        };
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(res.messages[0].msg, cw20_transfer(TREASURY, 15));
     }
 
     #[test]
     fn test_execute_withdraw_from_treasury_pool_when_there_are_no_fees() {
         let mut deps = mock_dependencies_kujira();
         let mut env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
@@ -1294,6 +5240,9 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1304,7 +5253,7 @@ mod tests {
 
         let info = mock_info(
             USER1,
-            &vec![Coin {
+            &[Coin {
                 denom: DENOM1.to_string(),
                 amount: Uint128::from(1000u128),
             }],
@@ -1316,7 +5265,7 @@ mod tests {
             name: "Round1".to_string(),
         };
 
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1345,13 +5294,9 @@ mod tests {
     fn test_execute_withdraw_from_treasury_pool_when_fees_exist() {
         let mut deps = mock_dependencies_kujira();
         let mut env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
@@ -1367,6 +5312,9 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1377,7 +5325,7 @@ mod tests {
 
         let info = mock_info(
             USER1,
-            &vec![Coin {
+            &[Coin {
                 denom: DENOM1.to_string(),
                 amount: Uint128::from(1000u128),
             }],
@@ -1385,11 +5333,24 @@ mod tests {
 
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Down,
+            round_name: "Round1".to_string(),
+        };
+        let info = mock_info(
+            ANYONE,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        );
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
         let msg = ExecuteMsg::StartRound {
             name: "Round1".to_string(),
         };
 
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1436,13 +5397,9 @@ mod tests {
     fn test_query_get_rounds() {
         let mut deps = mock_dependencies_kujira();
         let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
-        };
+        let msg = default_instantiate_msg();
 
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
@@ -1458,10 +5415,18 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        let msg = QueryMsg::GetRounds {};
+        let msg = QueryMsg::GetRounds {
+            start_after: None,
+            limit: None,
+            order: None,
+            filter: None,
+        };
 
         let bin = query(deps.as_ref(), env.clone(), msg).unwrap();
 
@@ -1471,8 +5436,13 @@ mod tests {
 
         let stop_time = new_timestamp + 300;
         let round = Round {
+            id: 1,
+            name: "Round1".to_string(),
             created_at: current_time,
             creator: info.sender,
+            asset_denom: ASSETDENOM.to_string(),
+            fee_bps: DEFAULT_FEE_BPS,
+            open_time: current_time,
             start_time: new_timestamp,
             stop_time,
             participants_count: 0,
@@ -1481,28 +5451,114 @@ mod tests {
             total_bet_amount: NativeBalance(vec![]),
             total_up_bet_amount: NativeBalance(vec![]),
             total_down_bet_amount: NativeBalance(vec![]),
-            is_started: false,
+            status: RoundStatus::Open,
+            status_updated_at: current_time,
             started_at: None,
-            is_stopped: false,
             stopped_at: None,
+            started_by: None,
+            stopped_by: None,
             start_price: None,
             stop_price: None,
+            outcome: None,
+            settlement_prices: vec![],
+            series: None,
+            claim_window: DEFAULT_CLAIM_WINDOW,
+            claimed_amount: NativeBalance(vec![]),
+            swept_amount: None,
+            start_grace_period: DEFAULT_START_GRACE_PERIOD,
+            stop_grace_period: DEFAULT_STOP_GRACE_PERIOD,
+            pool_caps: vec![],
+            max_side_ratio_bps: None,
         };
 
         assert_eq!(res.rounds, vec![round]);
+        assert_eq!(res.next_start_after, None);
     }
 
     #[test]
-    fn test_query_get_round() {
+    fn test_query_get_rounds_paginated() {
         let mut deps = mock_dependencies_kujira();
         let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
-            asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+        for id in 1..=5 {
+            let asset_denom = if id % 2 == 0 { ASSETDENOM2 } else { ASSETDENOM };
+            let creator = if id == 5 { ANYONE } else { ADMIN1 };
+            let msg = ExecuteMsg::CreateRound {
+                start_time,
+                name: format!("Round{}", id),
+                asset_denom: asset_denom.to_string(),
+                duration: None,
+                open_time: None,
+            };
+            let _res = execute(deps.as_mut(), env.clone(), mock_info(creator, &[]), msg).unwrap();
+        }
+
+        let msg = ExecuteMsg::StartRound {
+            name: "Round1".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let get_rounds =
+            |start_after: Option<u64>, order: Option<RoundOrder>, filter: Option<RoundFilter>| {
+                let msg = QueryMsg::GetRounds {
+                    start_after,
+                    limit: Some(2),
+                    order,
+                    filter,
+                };
+                let res: AllRoundsResponse =
+                    from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+                let ids: Vec<u64> = res.rounds.iter().map(|round| round.id).collect();
+                (ids, res.next_start_after)
+            };
+
+        assert_eq!(get_rounds(None, None, None), (vec![1, 2], Some(2)));
+        assert_eq!(get_rounds(Some(2), None, None), (vec![3, 4], Some(4)));
+        assert_eq!(get_rounds(Some(4), None, None), (vec![5], None));
+
+        let newest_first = Some(RoundOrder::NewestFirst);
+        assert_eq!(get_rounds(None, newest_first, None), (vec![5, 4], Some(4)));
+        assert_eq!(get_rounds(Some(2), newest_first, None), (vec![1], None));
+
+        // the filter applies to the rounds scanned for each page, pages can come back short
+        let filter = Some(RoundFilter {
+            asset_denom: Some(ASSETDENOM2.to_string()),
+            ..RoundFilter::default()
+        });
+        assert_eq!(get_rounds(None, None, filter.clone()), (vec![2], Some(2)));
+        assert_eq!(
+            get_rounds(Some(2), None, filter.clone()),
+            (vec![4], Some(4))
+        );
+        assert_eq!(get_rounds(Some(4), None, filter), (vec![], None));
+
+        let filter = Some(RoundFilter {
+            creator: Some(ANYONE.to_string()),
+            ..RoundFilter::default()
+        });
+        assert_eq!(get_rounds(None, None, filter.clone()), (vec![], Some(2)));
+        assert_eq!(get_rounds(Some(4), None, filter), (vec![5], None));
+
+        let filter = RoundFilter {
+            status: Some(RoundStatus::Locked),
+            ..RoundFilter::default()
         };
+        assert_eq!(get_rounds(None, None, Some(filter)), (vec![1], Some(2)));
+    }
+
+    #[test]
+    fn test_query_get_round() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
 
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
@@ -1518,6 +5574,9 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1533,8 +5592,13 @@ mod tests {
 
         let stop_time = new_timestamp + 300;
         let round = Round {
+            id: 1,
+            name: "Round1".to_string(),
             created_at: current_time,
             creator: info.sender,
+            asset_denom: ASSETDENOM.to_string(),
+            fee_bps: DEFAULT_FEE_BPS,
+            open_time: current_time,
             start_time: new_timestamp,
             stop_time,
             participants_count: 0,
@@ -1543,29 +5607,353 @@ mod tests {
             total_bet_amount: NativeBalance(vec![]),
             total_up_bet_amount: NativeBalance(vec![]),
             total_down_bet_amount: NativeBalance(vec![]),
+            status: RoundStatus::Open,
+            status_updated_at: current_time,
+            started_at: None,
+            stopped_at: None,
+            started_by: None,
+            stopped_by: None,
+            start_price: None,
+            stop_price: None,
+            outcome: None,
+            settlement_prices: vec![],
+            series: None,
+            claim_window: DEFAULT_CLAIM_WINDOW,
+            claimed_amount: NativeBalance(vec![]),
+            swept_amount: None,
+            start_grace_period: DEFAULT_START_GRACE_PERIOD,
+            stop_grace_period: DEFAULT_STOP_GRACE_PERIOD,
+            pool_caps: vec![],
+            max_side_ratio_bps: None,
+        };
+
+        assert_eq!(res.round, Some(round));
+        assert_eq!(res.status, Some(RoundStatus::Open));
+    }
+
+    #[test]
+    fn test_migrate_rounds_to_ids() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+        let now = env.block.time.seconds();
+
+        // seed the storage the way the contract stored it before round ids and statuses
+        let legacy_config = LegacyConfig {
+            admins: vec![Addr::unchecked(ADMIN1)],
+            asset_denom: ASSETDENOM.to_string(),
+            accepted_bet_denoms: vec![DENOM1.to_string()],
+        };
+        LEGACY_CONFIG
+            .save(deps.as_mut().storage, &legacy_config)
+            .unwrap();
+        let treasury_balance = TreasuryBalance {
+            balance: NativeBalance(vec![]),
+        };
+        TREASURYBALANCE
+            .save(deps.as_mut().storage, &treasury_balance)
+            .unwrap();
+        let stake = NativeBalance(vec![Coin {
+            denom: DENOM1.to_string(),
+            amount: Uint128::from(100u128),
+        }]);
+        let open_round = LegacyRound {
+            created_at: now - 1_000,
+            creator: Addr::unchecked(ADMIN1),
             is_started: false,
             started_at: None,
             is_stopped: false,
             stopped_at: None,
+            start_time: now + 600,
+            stop_time: now + 900,
+            participants_count: 0,
+            up_bets_count: 0,
+            down_bets_count: 0,
+            total_bet_amount: NativeBalance(vec![]),
+            total_up_bet_amount: NativeBalance(vec![]),
+            total_down_bet_amount: NativeBalance(vec![]),
             start_price: None,
             stop_price: None,
         };
+        let settled_round = LegacyRound {
+            created_at: now - 900,
+            is_started: true,
+            started_at: Some(now - 600),
+            is_stopped: true,
+            stopped_at: Some(now - 300),
+            start_time: now - 600,
+            stop_time: now - 300,
+            participants_count: 2,
+            up_bets_count: 1,
+            down_bets_count: 1,
+            total_bet_amount: stake.clone() + stake.clone(),
+            total_up_bet_amount: stake.clone(),
+            total_down_bet_amount: stake.clone(),
+            start_price: Some(Decimal::percent(100)),
+            stop_price: Some(Decimal::percent(150)),
+            ..open_round.clone()
+        };
+        LEGACY_ROUND
+            .save(deps.as_mut().storage, "Round2".to_string(), &open_round)
+            .unwrap();
+        LEGACY_ROUND
+            .save(deps.as_mut().storage, "Round1".to_string(), &settled_round)
+            .unwrap();
+        for (addr, side) in [(USER1, Side::Up), (ANYONE, Side::Down)] {
+            let legacy_bet = SingleDenomBet {
+                side,
+                amount: 100,
+                denom: DENOM1.to_string(),
+                win_claimed: false,
+                placed_at: now - 800,
+            };
+            LEGACY_BET
+                .save(
+                    deps.as_mut().storage,
+                    ("Round1".to_string(), Addr::unchecked(addr)),
+                    &legacy_bet,
+                )
+                .unwrap();
+        }
 
-        assert_eq!(res.round, Some(round));
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        assert_eq!(res.attributes[1], attr("migrated_rounds", "2"));
+
+        // the config keeps its asset and bet denom and gets the old 15% fee
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.asset_denoms, vec![ASSETDENOM.to_string()]);
+        assert_eq!(
+            config.accepted_bet_denoms,
+            vec![BetDenom::unlimited(DENOM1)]
+        );
+        assert_eq!(config.fee_bps, DEFAULT_FEE_BPS);
+
+        // ids follow the order the rounds were created in
+        for (id, name) in [(1, "Round2"), (2, "Round1")] {
+            let msg = QueryMsg::GetRound {
+                round_name: name.to_string(),
+            };
+            let res: RoundResponse =
+                from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            let round = res.round.unwrap();
+            assert_eq!(round.id, id);
+            assert_eq!(round.name, name);
+            assert_eq!(round.asset_denom, ASSETDENOM);
+            assert_eq!(round.fee_bps, DEFAULT_FEE_BPS);
+        }
+        let round = rounds().load(&deps.storage, 1).unwrap();
+        assert_eq!(round.status, RoundStatus::Open);
+        assert_eq!(round.outcome, None);
+        let round = rounds().load(&deps.storage, 2).unwrap();
+        assert_eq!(round.status, RoundStatus::Settled);
+        assert_eq!(round.started_at, Some(now - 600));
+        assert_eq!(round.stopped_at, Some(now - 300));
+        assert_eq!(round.status_updated_at, now - 300);
+        assert_eq!(round.outcome, Some(RoundOutcome::Up));
+        assert_eq!(round.settlement_prices.len(), 1);
+
+        // the winner of the settled round can claim the pool less the fee
+        let msg = ExecuteMsg::ClaimWin {
+            round_name: "Round1".to_string(),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER1, &[]), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: USER1.to_string(),
+                amount: vec![Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(170u128),
+                }],
+            })
+        );
+
+        let msg = QueryMsg::GetUserBet {
+            round_name: "Round1".to_string(),
+            user_addr: USER1.to_string(),
+        };
+        let res: UserBetResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(res.bet.unwrap().amount, stake);
+
+        // the migrated bets are indexed by user
+        let user_bets = bets()
+            .idx
+            .user
+            .prefix(Addr::unchecked(USER1))
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .count();
+        assert_eq!(user_bets, 1);
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: now + 600,
+            name: "Round3".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let res = execute(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(res.attributes[1], attr("round_id", "3"));
     }
 
     #[test]
-    fn test_query_user_bet() {
+    fn test_migrate_push_round() {
         let mut deps = mock_dependencies_kujira();
         let env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let now = env.block.time.seconds();
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
+        let legacy_config = LegacyConfig {
+            admins: vec![Addr::unchecked(ADMIN1)],
+            asset_denom: ASSETDENOM.to_string(),
+            accepted_bet_denoms: vec![DENOM1.to_string()],
+        };
+        LEGACY_CONFIG
+            .save(deps.as_mut().storage, &legacy_config)
+            .unwrap();
+        let stake = NativeBalance(vec![Coin {
+            denom: DENOM1.to_string(),
+            amount: Uint128::from(100u128),
+        }]);
+        // the price did not move, the old contract refunded both bettors on every claim
+        let push_round = LegacyRound {
+            created_at: now - 900,
+            creator: Addr::unchecked(ADMIN1),
+            is_started: true,
+            started_at: Some(now - 600),
+            is_stopped: true,
+            stopped_at: Some(now - 300),
+            start_time: now - 600,
+            stop_time: now - 300,
+            participants_count: 2,
+            up_bets_count: 1,
+            down_bets_count: 1,
+            total_bet_amount: stake.clone() + stake.clone(),
+            total_up_bet_amount: stake.clone(),
+            total_down_bet_amount: stake.clone(),
+            start_price: Some(Decimal::percent(100)),
+            stop_price: Some(Decimal::percent(100)),
+        };
+        LEGACY_ROUND
+            .save(deps.as_mut().storage, "Round1".to_string(), &push_round)
+            .unwrap();
+        for (addr, side) in [(USER1, Side::Up), (ANYONE, Side::Down)] {
+            let legacy_bet = SingleDenomBet {
+                side,
+                amount: 100,
+                denom: DENOM1.to_string(),
+                win_claimed: false,
+                placed_at: now - 800,
+            };
+            LEGACY_BET
+                .save(
+                    deps.as_mut().storage,
+                    ("Round1".to_string(), Addr::unchecked(addr)),
+                    &legacy_bet,
+                )
+                .unwrap();
+        }
+
+        let _res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+        // the refunds are taken as paid so they can't be claimed again or swept
+        let round = rounds().load(&deps.storage, 1).unwrap();
+        assert_eq!(round.outcome, Some(RoundOutcome::Push));
+        assert_eq!(round.claimed_amount, round.total_bet_amount);
+        for addr in [USER1, ANYONE] {
+            let bet = bets()
+                .load(&deps.storage, (1, Addr::unchecked(addr)))
+                .unwrap();
+            assert!(bet.win_claimed);
+        }
+        let msg = ExecuteMsg::ClaimWin {
+            round_name: "Round1".to_string(),
+        };
+        let err = execute(deps.as_mut(), env, mock_info(USER1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::WinAlreadyClaimed {}));
+    }
+
+    #[test]
+    fn test_round_status_transitions() {
+        let env = mock_env();
+        let mut round = Round {
+            id: 1,
+            name: "Round1".to_string(),
+            created_at: env.block.time.seconds(),
+            creator: Addr::unchecked(ADMIN1),
             asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
+            fee_bps: DEFAULT_FEE_BPS,
+            open_time: env.block.time.seconds(),
+            start_time: env.block.time.seconds() + 300,
+            stop_time: env.block.time.seconds() + 600,
+            participants_count: 0,
+            up_bets_count: 0,
+            down_bets_count: 0,
+            total_bet_amount: NativeBalance(vec![]),
+            total_up_bet_amount: NativeBalance(vec![]),
+            total_down_bet_amount: NativeBalance(vec![]),
+            status: RoundStatus::Scheduled,
+            status_updated_at: env.block.time.seconds(),
+            started_at: None,
+            stopped_at: None,
+            started_by: None,
+            stopped_by: None,
+            start_price: None,
+            stop_price: None,
+            outcome: None,
+            settlement_prices: vec![],
+            series: None,
+            claim_window: DEFAULT_CLAIM_WINDOW,
+            claimed_amount: NativeBalance(vec![]),
+            swept_amount: None,
+            start_grace_period: DEFAULT_START_GRACE_PERIOD,
+            stop_grace_period: DEFAULT_STOP_GRACE_PERIOD,
+            pool_caps: vec![],
+            max_side_ratio_bps: None,
         };
 
+        let err = round.transition(RoundStatus::Locked, 1).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidRoundTransition {
+                from: RoundStatus::Scheduled,
+                to: RoundStatus::Locked
+            }
+        ));
+        round.transition(RoundStatus::Open, 1).unwrap();
+
+        let err = round.transition(RoundStatus::Settled, 1).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidRoundTransition {
+                from: RoundStatus::Open,
+                to: RoundStatus::Settled
+            }
+        ));
+
+        round.transition(RoundStatus::Locked, 1).unwrap();
+        round.transition(RoundStatus::Settled, 2).unwrap();
+        assert_eq!(round.status_updated_at, 2);
+        assert_eq!(round.started_at, Some(1));
+        assert_eq!(round.stopped_at, Some(2));
+
+        let err = round.transition(RoundStatus::Cancelled, 3).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidRoundTransition {
+                from: RoundStatus::Settled,
+                to: RoundStatus::Cancelled
+            }
+        ));
+    }
+
+    #[test]
+    fn test_query_user_bet() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         let current_time = SystemTime::now();
@@ -1580,6 +5968,9 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1590,7 +5981,7 @@ mod tests {
 
         let info = mock_info(
             USER1,
-            &vec![Coin {
+            &[Coin {
                 denom: DENOM1.to_string(),
                 amount: Uint128::from(1000u128),
             }],
@@ -1609,26 +6000,284 @@ mod tests {
         let current_time = env.block.time.seconds();
         let new_bet = Bet {
             side: Side::Up,
-            amount: 1000u128,
-            denom: DENOM1.to_string(),
+            amount: NativeBalance(vec![Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(1000u128),
+            }]),
             win_claimed: false,
             placed_at: current_time,
+            lost: false,
         };
 
         assert_eq!(res.bet, Some(new_bet));
     }
 
     #[test]
-    fn test_query_treasury_pool_balance() {
+    fn test_query_user_bets() {
         let mut deps = mock_dependencies_kujira();
         let mut env = mock_env();
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
-        let msg = InstantiateMsg {
-            admins: vec![ADMIN1.to_string(), ADMIN2.to_string()],
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let start_time = env.block.time.seconds() + 300;
+        for name in ["Round1", "Round2", "Round3"] {
+            let msg = ExecuteMsg::CreateRound {
+                start_time,
+                name: name.to_string(),
+                asset_denom: ASSETDENOM.to_string(),
+                duration: None,
+                open_time: None,
+            };
+            let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let bets = [
+            (USER1, "Round1", Side::Up),
+            (USER1, "Round2", Side::Down),
+            (ANYONE, "Round2", Side::Up),
+            (USER1, "Round3", Side::Up),
+        ];
+        for (user, round_name, side) in bets {
+            let msg = ExecuteMsg::PlaceBet {
+                side,
+                round_name: round_name.to_string(),
+            };
+            let bettor = mock_info(
+                user,
+                &[Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+            );
+            let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        }
+
+        let msg = ExecuteMsg::StartRound {
+            name: "Round2".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let mut querier: MockQuerier<KujiraQuery> = MockQuerier::new(&[]);
+        // update querier to have the price go down
+        querier = querier.with_custom_handler(|query: &KujiraQuery| match query {
+            KujiraQuery::Oracle(OracleQuery::ExchangeRate { denom: _ }) => {
+                let exchange_rate_response = ExchangeRateResponse {
+                    rate: Decimal::from_str("1.10").unwrap(),
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&exchange_rate_response).unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+        deps.querier = querier;
+
+        // Round1 and Round3 were never started so they can be refunded
+        env.block.time = Timestamp::from_seconds(start_time + 301);
+        let msg = ExecuteMsg::StopRound {
+            name: "Round2".to_string(),
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let get_user_bets = |user: &str, start_after: Option<u64>| {
+            let msg = QueryMsg::GetUserBets {
+                user_addr: user.to_string(),
+                start_after,
+                limit: Some(2),
+            };
+            let res: UserBetsResponse =
+                from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            res
+        };
+        let coins = |amount: u128| {
+            vec![Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(amount),
+            }]
+        };
+
+        let res = get_user_bets(USER1, None);
+        assert_eq!(res.next_start_after, Some(2));
+        let summary: Vec<_> = res
+            .bets
+            .iter()
+            .map(|b| (b.round_name.as_str(), b.round_status, b.claimable.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Round1", RoundStatus::Cancelled, coins(100)),
+                ("Round2", RoundStatus::Settled, coins(170)),
+            ]
+        );
+
+        let res = get_user_bets(USER1, Some(2));
+        assert_eq!(res.next_start_after, None);
+        assert_eq!(res.bets.len(), 1);
+        assert_eq!(res.bets[0].round_id, 3);
+        assert_eq!(res.bets[0].bet.side, Side::Up);
+
+        let res = get_user_bets(ANYONE, None);
+        assert_eq!(res.bets.len(), 1);
+        assert_eq!(res.bets[0].round_name, "Round2");
+        assert_eq!(res.bets[0].claimable, vec![]);
+    }
+
+    #[test]
+    fn test_query_round_odds() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: env.block.time.seconds() + 300,
+            name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let bets = [
+            (USER1, Side::Up, DENOM1, 1000u128),
+            (ANYONE, Side::Down, DENOM2, 3000u128),
+        ];
+        for (user, side, denom, amount) in bets {
+            let msg = ExecuteMsg::PlaceBet {
+                side,
+                round_name: "Round1".to_string(),
+            };
+            let bettor = mock_info(
+                user,
+                &[Coin {
+                    denom: denom.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+            );
+            let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        }
+
+        let msg = QueryMsg::GetRoundOdds {
+            round_name: "Round1".to_string(),
+        };
+        let res: RoundOddsResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+
+        // every denom is priced at 1.23 by the mock oracle
+        assert_eq!(res.prices.len(), 2);
+        assert_eq!(res.up.value, Decimal256::from_str("1230").unwrap());
+        assert_eq!(res.down.value, Decimal256::from_str("3690").unwrap());
+        assert_eq!(
+            res.up.multiplier,
+            Some(Decimal256::from_str("3.4").unwrap())
+        );
+        assert_eq!(
+            res.down.multiplier,
+            Some(Decimal256::from_str("1.133333333333333333").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_query_round_bets() {
+        let mut deps = mock_dependencies_kujira();
+        let env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
+
+        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::CreateRound {
+            start_time: env.block.time.seconds() + 300,
+            name: "Round1".to_string(),
             asset_denom: ASSETDENOM.to_string(),
-            accepted_bet_denoms: vec![String::from(DENOM1), String::from(DENOM2)],
+            duration: None,
+            open_time: None,
+        };
+        let _res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let bets = [
+            (USER1, Side::Down),
+            (KEEPER2, Side::Up),
+            (KEEPER1, Side::Down),
+            (ANYONE, Side::Up),
+        ];
+        for (user, side) in bets {
+            let msg = ExecuteMsg::PlaceBet {
+                side,
+                round_name: "Round1".to_string(),
+            };
+            let bettor = mock_info(
+                user,
+                &[Coin {
+                    denom: DENOM1.to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+            );
+            let _res = execute(deps.as_mut(), env.clone(), bettor, msg).unwrap();
+        }
+
+        let get_round_bets = |side: Option<Side>, start_after: Option<&str>| {
+            let msg = QueryMsg::GetRoundBets {
+                round_name: "Round1".to_string(),
+                side,
+                start_after: start_after.map(String::from),
+                limit: Some(2),
+            };
+            let res: RoundBetsResponse =
+                from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            let users: Vec<String> = res.bets.iter().map(|b| b.user.to_string()).collect();
+            (users, res.next_start_after)
+        };
+
+        assert_eq!(
+            get_round_bets(None, None),
+            (
+                vec![ANYONE.to_string(), KEEPER1.to_string()],
+                Some(KEEPER1.to_string())
+            )
+        );
+        assert_eq!(
+            get_round_bets(None, Some(KEEPER1)),
+            (
+                vec![KEEPER2.to_string(), USER1.to_string()],
+                Some(USER1.to_string())
+            )
+        );
+        assert_eq!(get_round_bets(None, Some(USER1)), (vec![], None));
+        // the side applies to the bets scanned for each page, pages can come back short
+        assert_eq!(
+            get_round_bets(Some(Side::Up), None),
+            (vec![ANYONE.to_string()], Some(KEEPER1.to_string()))
+        );
+        assert_eq!(
+            get_round_bets(Some(Side::Up), Some(KEEPER1)),
+            (vec![KEEPER2.to_string()], Some(USER1.to_string()))
+        );
+
+        let msg = QueryMsg::GetRoundBets {
+            round_name: "Round2".to_string(),
+            side: None,
+            start_after: None,
+            limit: None,
         };
+        let err = query(deps.as_ref(), env, msg).unwrap_err();
+        assert!(matches!(err, StdError::NotFound { .. }));
+    }
+
+    #[test]
+    fn test_query_treasury_pool_balance() {
+        let mut deps = mock_dependencies_kujira();
+        let mut env = mock_env();
+        let info = mock_info(ADMIN1, &[]);
+
+        let msg = default_instantiate_msg();
 
         let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
@@ -1644,6 +6293,9 @@ mod tests {
         let msg = ExecuteMsg::CreateRound {
             start_time: new_timestamp,
             name: "Round1".to_string(),
+            asset_denom: ASSETDENOM.to_string(),
+            duration: None,
+            open_time: None,
         };
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
@@ -1654,19 +6306,32 @@ mod tests {
 
         let info = mock_info(
             USER1,
-            &vec![Coin {
+            &[Coin {
                 denom: DENOM1.to_string(),
-                amount: Uint128::from(100u128),
+                amount: Uint128::from(50u128),
             }],
         );
 
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
+        let msg = ExecuteMsg::PlaceBet {
+            side: Side::Down,
+            round_name: "Round1".to_string(),
+        };
+        let info = mock_info(
+            ANYONE,
+            &[Coin {
+                denom: DENOM1.to_string(),
+                amount: Uint128::from(50u128),
+            }],
+        );
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
         let msg = ExecuteMsg::StartRound {
             name: "Round1".to_string(),
         };
 
-        let info = mock_info(ADMIN1, &vec![]);
+        let info = mock_info(ADMIN1, &[]);
 
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
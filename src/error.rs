@@ -1,8 +1,10 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 use cw_utils::PaymentError;
 
+use crate::state::RoundStatus;
+
 #[derive(Error, Debug)]
 pub enum ContractError {
     #[error("{0}")]
@@ -14,6 +16,18 @@ pub enum ContractError {
     #[error("{0}")]
     Payment(#[from] PaymentError),
 
+    #[error("Fee can't be more than {max_fee_bps} basis points")]
+    FeeTooHigh { max_fee_bps: u16 },
+
+    #[error("Keeper reward can't be more than {max_keeper_reward_bps} basis points")]
+    KeeperRewardTooHigh { max_keeper_reward_bps: u16 },
+
+    #[error("Invalid round timings message: {message:?}")]
+    InvalidRoundTimings { message: String },
+
+    #[error("Round duration should be between {min} and {max} seconds")]
+    InvalidRoundDuration { min: u64, max: u64 },
+
     #[error("Invalid start time message: {message:?}")]
     InvalidStartTime { message: String },
 
@@ -26,6 +40,9 @@ pub enum ContractError {
     #[error("Round with the provided name already exists")]
     RoundAlreadyExists {},
 
+    #[error("Round series with the provided name already exists")]
+    SeriesAlreadyExists {},
+
     #[error("Round with the provided name already ended")]
     RoundAlreadyEnded {},
 
@@ -35,9 +52,47 @@ pub enum ContractError {
     #[error("Round with the provided name has already started")]
     RoundAlreadyStarted {},
 
+    #[error("Round start time has not yet reached")]
+    RoundNotReadyToStart {},
+
     #[error("Round stop time already passed")]
     RoundStopTimePassed {},
 
+    #[error("Oracle price could not be read: {message}")]
+    OracleUnavailable { message: String },
+
+    #[error("Oracle price of {denom} is zero")]
+    ZeroOraclePrice { denom: String },
+
+    #[error(
+        "Stop price moved more than {max_price_deviation_bps} basis points from the start price"
+    )]
+    PriceDeviationTooHigh { max_price_deviation_bps: u32 },
+
+    #[error("Max price deviation has to be more than 0 basis points")]
+    InvalidPriceDeviation {},
+
+    #[error("Round was not started within its start grace period")]
+    RoundStartWindowPassed {},
+
+    #[error("Round was not stopped within its stop grace period")]
+    RoundStopWindowPassed {},
+
+    #[error("Round with the provided name has not started")]
+    RoundNotStarted {},
+
+    #[error("Round with the provided name is not open for bets yet")]
+    RoundNotOpen {},
+
+    #[error("Round can't move from {from:?} to {to:?}")]
+    InvalidRoundTransition { from: RoundStatus, to: RoundStatus },
+
+    #[error("Round with the provided name has been cancelled")]
+    RoundCancelled {},
+
+    #[error("Round with the provided name has not been cancelled")]
+    RoundNotCancelled {},
+
     #[error("Round stop time has not yet reached")]
     RoundStillInProgress {},
 
@@ -47,6 +102,9 @@ pub enum ContractError {
     #[error("A maximum of one coin can be deposited")]
     TooManyCoins {},
 
+    #[error("Rounds can't be created for the provided asset")]
+    AssetNotSupported {},
+
     #[error("The deposited denom is not supported")]
     DenomNotSupported {},
 
@@ -62,15 +120,54 @@ pub enum ContractError {
     #[error("Can't edit bet to set the same side as the existing one")]
     DuplicateBetSide {},
 
+    #[error("Bet in {denom} is below the minimum of {min_bet}")]
+    BetTooSmall { denom: String, min_bet: Uint128 },
+
+    #[error("Bet in {denom} is above the maximum of {max_bet}")]
+    BetTooLarge { denom: String, max_bet: Uint128 },
+
+    #[error("Minimum bet for {denom} can't be more than its maximum bet")]
+    InvalidBetLimits { denom: String },
+
+    #[error("The {denom} pool of the provided round is capped at {cap}")]
+    PoolCapReached { denom: String, cap: Uint128 },
+
+    #[error("The provided side would be worth too much more than the other side")]
+    SideImbalanceTooHigh {},
+
+    #[error("Max side ratio can't be less than {min_side_ratio_bps} basis points")]
+    SideRatioTooLow { min_side_ratio_bps: u32 },
+
+    #[error("Side switch penalty can't be more than {max_side_switch_penalty_bps} basis points")]
+    SideSwitchPenaltyTooHigh { max_side_switch_penalty_bps: u16 },
+
     #[error("You have already claimed your win from the privided round")]
     WinAlreadyClaimed {},
 
+    #[error("You have already claimed your refund from the provided round")]
+    RefundAlreadyClaimed {},
+
     #[error("Fees for this round have already been claimed")]
     FeesAlreadyClaimed {},
 
     #[error("You cannot claim win from the provided round because you lost")]
     YouLost {},
 
+    #[error("There is nothing to claim from the provided rounds")]
+    NothingToClaim {},
+
+    #[error("Every bet of the provided round has already been paid out")]
+    RoundAlreadyDistributed {},
+
+    #[error("The claim window of the provided round is over")]
+    ClaimWindowExpired {},
+
+    #[error("The claim window of the provided round is not over yet")]
+    ClaimWindowNotExpired {},
+
+    #[error("The unclaimed amount of the provided round has already been swept")]
+    RoundAlreadySwept {},
+
     #[error("There is insufficient balance in treasury to withdraw the required amount")]
     InsufficientTreasuryBalance {},
 }
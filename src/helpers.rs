@@ -1,7 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdResult, WasmMsg};
+use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, StdResult, WasmMsg};
+use cw20::Cw20ExecuteMsg;
 
 use crate::msg::ExecuteMsg;
 
@@ -25,3 +26,49 @@ impl CwTemplateContract {
         .into())
     }
 }
+
+// cw20 tokens are tracked alongside native coins in the same balances, under a denom made
+// from the token contract address
+const CW20_DENOM_PREFIX: &str = "cw20:";
+
+/// returns the denom a cw20 token is tracked under
+pub fn cw20_denom(contract_addr: impl AsRef<str>) -> String {
+    format!("{}{}", CW20_DENOM_PREFIX, contract_addr.as_ref())
+}
+
+/// returns the token contract address if the denom is a cw20 token
+pub fn cw20_address(denom: &str) -> Option<&str> {
+    denom.strip_prefix(CW20_DENOM_PREFIX)
+}
+
+/// builds the messages paying coins to an address, native coins are sent together in a bank
+/// transfer and every cw20 token is sent with its own transfer
+pub fn send_coins(to_address: impl Into<String>, coins: Vec<Coin>) -> StdResult<Vec<CosmosMsg>> {
+    let to_address = to_address.into();
+    let (cw20_coins, native_coins): (Vec<Coin>, Vec<Coin>) = coins
+        .into_iter()
+        .filter(|coin| !coin.amount.is_zero())
+        .partition(|coin| cw20_address(&coin.denom).is_some());
+    let mut messages = vec![];
+    if !native_coins.is_empty() {
+        messages.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: to_address.clone(),
+            amount: native_coins,
+        }));
+    }
+    for coin in cw20_coins {
+        let contract_addr = cw20_address(&coin.denom).unwrap_or_default().to_string();
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr,
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: to_address.clone(),
+                    amount: coin.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        );
+    }
+    Ok(messages)
+}
@@ -2,6 +2,8 @@ pub mod contract;
 mod error;
 pub mod helpers;
 pub mod msg;
+pub mod payout;
+pub mod price;
 pub mod state;
 
 pub use crate::error::ContractError;
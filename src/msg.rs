@@ -1,14 +1,66 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::{Bet, Round, Side, TreasuryBalance};
+use cosmwasm_std::{Addr, Coin, Decimal, Decimal256, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::payout::PayoutKind;
+use crate::state::{
+    Bet, BetDenom, Config, DenomPrice, Round, RoundSeries, RoundStatus, Side, TreasuryBalance,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct InstantiateMsg {
     pub admins: Vec<String>,
-    pub asset_denom: String,
-    pub accepted_bet_denoms: Vec<String>,
+    pub asset_denoms: Vec<String>,
+    pub accepted_bet_denoms: Vec<BetDenom>,
+    pub fee_bps: Option<u16>,
+    pub min_round_duration: Option<u64>,
+    pub max_round_duration: Option<u64>,
+    pub min_lead_time: Option<u64>,
+    pub start_grace_period: Option<u64>,
+    pub stop_grace_period: Option<u64>,
+    pub keeper_reward_bps: Option<u16>,
+    // seconds bettors have to claim after a round is settled or cancelled, defaults to 30 days
+    pub claim_window: Option<u64>,
+    pub side_switch_penalty_bps: Option<u16>,
+    pub accepted_cw20_tokens: Option<Vec<AcceptedCw20Token>>,
+    pub pool_caps: Option<Vec<Coin>>,
+    // e.g. 30_000 lets a side be worth at most 3 times the other side
+    pub max_side_ratio_bps: Option<u32>,
+    pub max_price_deviation_bps: Option<u32>,
+    pub oracle_grace_period: Option<u64>,
+    // denoms not listed are priced by the oracle
+    pub price_sources: Option<Vec<AssetPriceSource>>,
+}
+
+// a cw20 token users are allowed to bet with and the oracle denom used to price it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AcceptedCw20Token {
+    pub address: String,
+    pub oracle_denom: String,
+    // bet limits for the token, see state::BetDenom
+    #[serde(default)]
+    pub min_bet: Uint128,
+    pub max_bet: Option<Uint128>,
+}
+
+// where the price of a denom is read from, see state::PriceSource
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSourceMsg {
+    Oracle {},
+    Fin { pair: String },
+    Reporter { reporter: String, max_age: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AssetPriceSource {
+    pub denom: String,
+    pub source: PriceSourceMsg,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -20,11 +72,40 @@ pub enum ExecuteMsg {
     CreateRound {
         start_time: u64,
         name: String,
+        asset_denom: String,
+        // round duration in seconds, defaults to 5 mins
+        duration: Option<u64>,
+        // time the round starts accepting bets, defaults to the creation time
+        open_time: Option<u64>,
+    },
+    // opens a new round of the series every time the latest one is stopped or cancelled
+    CreateRoundSeries {
+        name: String,
+        asset_denom: String,
+        // start time of the first round
+        start_time: u64,
+        // seconds between the start times of consecutive rounds
+        interval: u64,
+        // round duration in seconds, defaults to 5 mins
+        duration: Option<u64>,
+        bet_denoms: Vec<String>,
+    },
+    EndRoundSeries {
+        name: String,
     },
+    // placing another bet on the same side adds to the existing bet, with any accepted denom
     PlaceBet {
         side: Side,
         round_name: String,
     },
+    // bets with cw20 tokens, the msg of the cw20 send is a ReceiveMsg
+    Receive(Cw20ReceiveMsg),
+    // moves the sender's bet to the other side before the round starts, minus the side switch
+    // penalty
+    SwitchBetSide {
+        side: Side,
+        round_name: String,
+    },
     WithdrawBet {
         round_name: String,
     },
@@ -37,23 +118,104 @@ pub enum ExecuteMsg {
     ClaimWin {
         round_name: String,
     },
+    CancelRound {
+        name: String,
+    },
+    ClaimRefund {
+        round_name: String,
+    },
+    // claims wins and refunds from the given rounds in a single transfer, lost rounds are skipped
+    ClaimWins {
+        round_names: Vec<String>,
+    },
+    // pays the wins and refunds of a finished round directly to the bettors, a page of bets at a
+    // time. every call carries on from where the previous one stopped
+    DistributeRound {
+        round_name: String,
+        limit: Option<u32>,
+    },
+    // moves what was left unclaimed from a round to the treasury once its claim window is over
+    SweepExpired {
+        round_name: String,
+    },
+    // posts the current price of a denom priced by a trusted reporter, only its reporter can
+    ReportPrice {
+        denom: String,
+        price: Decimal,
+    },
+    // claims wins and refunds from the rounds the sender bet in a page of bets at a time,
+    // start_after is the next_start_after attribute of the previous page
+    ClaimAll {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     WithdrawFromPool {
         to_address: String,
         denom: String,
         amount: u128,
     },
     UpdateAcceptedBetDenoms {
-        accepted_bet_denoms: Vec<String>,
+        accepted_bet_denoms: Vec<BetDenom>,
     },
-    UpdateAssetDenom {
-        asset_denom: String,
+    UpdateAcceptedCw20Tokens {
+        accepted_cw20_tokens: Vec<AcceptedCw20Token>,
     },
+    UpdateAssetDenoms {
+        asset_denoms: Vec<String>,
+    },
+    UpdatePriceSources {
+        price_sources: Vec<AssetPriceSource>,
+    },
+    UpdateFee {
+        fee_bps: u16,
+    },
+    UpdateKeeperReward {
+        keeper_reward_bps: u16,
+    },
+    UpdateClaimWindow {
+        claim_window: u64,
+    },
+    UpdateSideSwitchPenalty {
+        side_switch_penalty_bps: u16,
+    },
+    // limits applied to rounds created from now on
+    UpdateRoundLimits {
+        pool_caps: Vec<Coin>,
+        max_side_ratio_bps: Option<u32>,
+    },
+    // guards against bad oracle prices when starting and stopping rounds
+    UpdateOracleGuards {
+        max_price_deviation_bps: Option<u32>,
+        oracle_grace_period: Option<u64>,
+    },
+    UpdateRoundTimings {
+        min_round_duration: u64,
+        max_round_duration: u64,
+        min_lead_time: u64,
+        start_grace_period: u64,
+        stop_grace_period: u64,
+    },
+}
+
+// hook messages accepted with cw20 tokens sent to the contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    PlaceBet { side: Side, round_name: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    GetRounds {},
+    // rounds are ordered by id, start_after is the next_start_after of the previous page. the
+    // filter applies to the limit rounds scanned for a page so a page can come back short
+    GetRounds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RoundOrder>,
+        filter: Option<RoundFilter>,
+    },
+    GetConfig {},
     GetTreasuryBalance {},
     GetRound {
         round_name: String,
@@ -62,11 +224,62 @@ pub enum QueryMsg {
         round_name: String,
         user_addr: String,
     },
+    GetRoundSeries {
+        name: String,
+    },
+    // bets are ordered by round id, start_after is the next_start_after of the previous page
+    GetUserBets {
+        user_addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // previews what claiming from a round pays the user without claiming it
+    GetClaimable {
+        round_name: String,
+        user_addr: String,
+    },
+    // pool values and payout multipliers of both sides, valued with the oracle prices until
+    // the round is settled and with the settlement prices after
+    GetRoundOdds {
+        round_name: String,
+    },
+    // bets are ordered by user address, start_after is the next_start_after of the previous page.
+    // side applies to the limit bets scanned for a page so a page can come back short
+    GetRoundBets {
+        round_name: String,
+        side: Option<Side>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundOrder {
+    OldestFirst,
+    NewestFirst,
+}
+
+// only rounds matching every given field are returned
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RoundFilter {
+    // status of the round at query time, stale rounds are reported as cancelled
+    pub status: Option<RoundStatus>,
+    pub asset_denom: Option<String>,
+    pub creator: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct AllRoundsResponse {
     pub rounds: Vec<Round>,
+    // id to pass as start_after to get the next page, none when there are no more rounds
+    pub next_start_after: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ConfigResponse {
+    pub config: Config,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -77,6 +290,13 @@ pub struct TreasuryBalanceResponse {
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct RoundResponse {
     pub round: Option<Round>,
+    // status of the round at query time, stale rounds are reported as cancelled
+    pub status: Option<RoundStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoundSeriesResponse {
+    pub series: Option<RoundSeries>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -84,6 +304,68 @@ pub struct UserBetResponse {
     pub bet: Option<Bet>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct UserRoundBet {
+    pub round_id: u64,
+    pub round_name: String,
+    // status of the round at query time, stale rounds are reported as cancelled
+    pub round_status: RoundStatus,
+    pub bet: Bet,
+    // what the user can claim from the round right now
+    pub claimable: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct UserBetsResponse {
+    pub bets: Vec<UserRoundBet>,
+    // round id to pass as start_after to get the next page, none when there are no more bets
+    pub next_start_after: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoundBet {
+    pub user: Addr,
+    pub bet: Bet,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoundBetsResponse {
+    pub bets: Vec<RoundBet>,
+    // user address to pass as start_after to get the next page, none when there are no more bets
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ClaimableResponse {
+    // status of the round at query time, stale rounds are reported as cancelled
+    pub round_status: RoundStatus,
+    // none until the round is settled or cancelled
+    pub kind: Option<PayoutKind>,
+    // what the claim pays in every denom, even if it was already claimed
+    pub coins: Vec<Coin>,
+    pub claimed: bool,
+    // nothing can be claimed once the claim window of the round is over
+    pub claim_window_expired: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SideOdds {
+    pub pool: Vec<Coin>,
+    // value of every denom in the pool in the unit of the oracle prices
+    pub value: Decimal256,
+    // how many times its value a bet on this side gets back if the side wins, after fees.
+    // none while nobody bet on this side
+    pub multiplier: Option<Decimal256>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoundOddsResponse {
+    pub up: SideOdds,
+    pub down: SideOdds,
+    // prices the pools were valued with
+    pub prices: Vec<DenomPrice>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub enum MigrateMsg {}
+pub struct MigrateMsg {}
@@ -0,0 +1,516 @@
+use cosmwasm_std::{Coin, Decimal, Decimal256, StdError, StdResult, Uint128, Uint256, Uint512};
+use cw_utils::NativeBalance;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{Bet, DenomPrice, Round, RoundOutcome, RoundStatus, Side};
+
+// fee rate in basis points taken from the pool of every round whose price moved
+pub const DEFAULT_FEE_BPS: u16 = 1_500;
+// highest fee rate admins are allowed to set
+pub const MAX_FEE_BPS: u16 = 3_000;
+// highest share of the fee a single keeper can be paid, two keepers can never take it all
+pub const MAX_KEEPER_REWARD_BPS: u16 = 5_000;
+// highest share of a bet admins can take as a penalty for switching its side
+pub const MAX_SIDE_SWITCH_PENALTY_BPS: u16 = 1_000;
+
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+// what a bet is entitled to once a round has been stopped
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutKind {
+    Win,
+    Refund,
+    Loss,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Payout {
+    pub kind: PayoutKind,
+    pub coins: Vec<Coin>,
+}
+
+// returns the side that won a round, None means the price did not move
+pub fn winning_side(start_price: Decimal, stop_price: Decimal) -> Option<Side> {
+    if stop_price > start_price {
+        Some(Side::Up)
+    } else if stop_price < start_price {
+        Some(Side::Down)
+    } else {
+        None
+    }
+}
+
+// decides the outcome of a round once its stop price is recorded.
+// a round where one side has no bets, which includes a round with a single participant,
+// is a push just like a round where the price did not move
+pub fn round_outcome(round: &Round) -> StdResult<RoundOutcome> {
+    let start_price = round
+        .start_price
+        .ok_or_else(|| StdError::generic_err("round has no start price"))?;
+    let stop_price = round
+        .stop_price
+        .ok_or_else(|| StdError::generic_err("round has no stop price"))?;
+    if round.up_bets_count == 0 || round.down_bets_count == 0 {
+        return Ok(RoundOutcome::Push);
+    }
+    Ok(match winning_side(start_price, stop_price) {
+        Some(Side::Up) => RoundOutcome::Up,
+        Some(Side::Down) => RoundOutcome::Down,
+        None => RoundOutcome::Push,
+    })
+}
+
+// fee taken by the treasury from an amount, rounded down
+pub fn fee_amount(amount: Uint128, fee_bps: u16) -> Uint128 {
+    amount.multiply_ratio(u128::from(fee_bps), BPS_DENOMINATOR)
+}
+
+// share of a round fee paid to a keeper, rounded down
+pub fn keeper_reward(fee: Uint128, keeper_reward_bps: u16) -> Uint128 {
+    fee.multiply_ratio(u128::from(keeper_reward_bps), BPS_DENOMINATOR)
+}
+
+// what is left of an amount after the fee has been taken, rounded down
+pub fn net_of_fee(amount: Uint128, fee_bps: u16) -> Uint128 {
+    amount.multiply_ratio(BPS_DENOMINATOR - u128::from(fee_bps), BPS_DENOMINATOR)
+}
+
+// computes what a bet is owed from a settled round, a push refunds the full stake.
+// a winner gets their own stake back in every denom it was placed with plus a share of the
+// losing pool in every denom, both net of fees. the share of the losing pool is the value of
+// the bet relative to the value of the whole winning side, using the bet denom prices recorded
+// when the round stopped. every amount is rounded down so the payouts plus the fee never exceed
+// the pool, the few units of dust left over stay in the contract. the fee rate is the one
+// snapshotted in the round
+pub fn compute_payout(round: &Round, bet: &Bet) -> StdResult<Payout> {
+    let fee_bps = round.fee_bps;
+    let outcome = round
+        .outcome
+        .ok_or_else(|| StdError::generic_err("round has not been settled"))?;
+
+    let stake = &bet.amount;
+    let (winning_side, winning_pool, losing_pool) = match outcome {
+        RoundOutcome::Push => {
+            return Ok(Payout {
+                kind: PayoutKind::Refund,
+                coins: stake.clone().into_vec(),
+            })
+        }
+        RoundOutcome::Up => (
+            Side::Up,
+            &round.total_up_bet_amount,
+            &round.total_down_bet_amount,
+        ),
+        RoundOutcome::Down => (
+            Side::Down,
+            &round.total_down_bet_amount,
+            &round.total_up_bet_amount,
+        ),
+    };
+    if bet.side != winning_side {
+        return Ok(Payout {
+            kind: PayoutKind::Loss,
+            coins: vec![],
+        });
+    }
+
+    let bet_value = balance_value(stake, &round.settlement_prices)?;
+    let winning_value = balance_value(winning_pool, &round.settlement_prices)?;
+
+    let mut coins = NativeBalance(
+        stake
+            .0
+            .iter()
+            .map(|coin| Coin {
+                denom: coin.denom.clone(),
+                amount: net_of_fee(coin.amount, fee_bps),
+            })
+            .collect(),
+    );
+    for coin in losing_pool.0.iter() {
+        let sharable_amount = net_of_fee(coin.amount, fee_bps);
+        coins += Coin {
+            denom: coin.denom.clone(),
+            amount: pro_rata(sharable_amount, bet_value, winning_value)?,
+        };
+    }
+    coins.normalize();
+
+    Ok(Payout {
+        kind: PayoutKind::Win,
+        coins: coins.into_vec(),
+    })
+}
+
+// what a bet is owed from a round that is in the given status, whether or not it was claimed.
+// a settled round pays what compute_payout returns and a cancelled round refunds the stake,
+// None means the round has not finished yet
+pub fn bet_payout(round: &Round, bet: &Bet, status: RoundStatus) -> StdResult<Option<Payout>> {
+    match status {
+        RoundStatus::Settled => Ok(Some(compute_payout(round, bet)?)),
+        RoundStatus::Cancelled => Ok(Some(Payout {
+            kind: PayoutKind::Refund,
+            coins: bet.amount.clone().into_vec(),
+        })),
+        RoundStatus::Scheduled | RoundStatus::Open | RoundStatus::Locked => Ok(None),
+    }
+}
+
+// coins a bet can claim from a round that is in the given status, nothing once it is claimed
+pub fn claimable_coins(round: &Round, bet: &Bet, status: RoundStatus) -> StdResult<Vec<Coin>> {
+    if bet.win_claimed {
+        return Ok(vec![]);
+    }
+    let payout = bet_payout(round, bet, status)?;
+    Ok(payout.map(|payout| payout.coins).unwrap_or_default())
+}
+
+// value of a coin expressed in price atomics, kept as an integer so no precision is lost
+fn coin_value(coin: &Coin, prices: &[DenomPrice]) -> StdResult<Uint256> {
+    let price = prices
+        .iter()
+        .find(|p| p.denom == coin.denom)
+        .ok_or_else(|| StdError::generic_err(format!("no price recorded for {}", coin.denom)))?;
+    Ok(price.price.atomics().full_mul(coin.amount))
+}
+
+pub fn balance_value(balance: &NativeBalance, prices: &[DenomPrice]) -> StdResult<Uint256> {
+    balance.0.iter().try_fold(Uint256::zero(), |total, coin| {
+        Ok(total.checked_add(coin_value(coin, prices)?)?)
+    })
+}
+
+// how many times its value a bet on a side gets back if that side wins, after fees.
+// values are the ones returned by balance_value. None means nobody bet on the side yet and a
+// side whose opposing side has no bets can only be a push that refunds the stake
+pub fn payout_multiplier(
+    side_value: Uint256,
+    opposing_value: Uint256,
+    fee_bps: u16,
+) -> StdResult<Option<Decimal256>> {
+    if side_value.is_zero() {
+        return Ok(None);
+    }
+    if opposing_value.is_zero() {
+        return Ok(Some(Decimal256::one()));
+    }
+    let pool_value = side_value.checked_add(opposing_value)?;
+    let net_pool_value =
+        pool_value.checked_mul(Uint256::from(BPS_DENOMINATOR - u128::from(fee_bps)))?;
+    let multiplier = Decimal256::checked_from_ratio(
+        net_pool_value,
+        side_value.checked_mul(Uint256::from(BPS_DENOMINATOR))?,
+    )
+    .map_err(|e| StdError::generic_err(e.to_string()))?;
+    Ok(Some(multiplier))
+}
+
+// amount * part / whole rounded down, computed in 512 bits so it cannot overflow
+fn pro_rata(amount: Uint128, part: Uint256, whole: Uint256) -> StdResult<Uint128> {
+    if whole.is_zero() {
+        return Err(StdError::generic_err("winning side has no value"));
+    }
+    let share = Uint256::from(amount).full_mul(part) / Uint512::from(whole);
+    Ok(Uint128::try_from(share)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{
+        DEFAULT_CLAIM_WINDOW, DEFAULT_START_GRACE_PERIOD, DEFAULT_STOP_GRACE_PERIOD,
+    };
+    use cosmwasm_std::Addr;
+    use std::str::FromStr;
+
+    const DENOM1: &str = "denom1";
+    const DENOM2: &str = "denom2";
+
+    fn coin(amount: u128, denom: &str) -> Coin {
+        Coin {
+            denom: denom.to_string(),
+            amount: Uint128::from(amount),
+        }
+    }
+
+    fn bet(side: Side, amount: u128, denom: &str) -> Bet {
+        Bet {
+            side,
+            amount: NativeBalance(vec![coin(amount, denom)]),
+            win_claimed: false,
+            placed_at: 0,
+            lost: false,
+        }
+    }
+
+    fn price(denom: &str, price: &str) -> DenomPrice {
+        DenomPrice {
+            denom: denom.to_string(),
+            price: Decimal::from_str(price).unwrap(),
+        }
+    }
+
+    // builds a stopped round from the given bets
+    fn round(bets: &[Bet], start_price: &str, stop_price: &str, prices: Vec<DenomPrice>) -> Round {
+        let mut round = Round {
+            id: 1,
+            name: "round".to_string(),
+            created_at: 0,
+            creator: Addr::unchecked("creator"),
+            asset_denom: "asset".to_string(),
+            fee_bps: DEFAULT_FEE_BPS,
+            status: RoundStatus::Settled,
+            status_updated_at: 300,
+            started_at: None,
+            stopped_at: None,
+            started_by: None,
+            stopped_by: None,
+            open_time: 0,
+            start_time: 0,
+            stop_time: 300,
+            participants_count: 0,
+            up_bets_count: 0,
+            down_bets_count: 0,
+            total_bet_amount: NativeBalance(vec![]),
+            total_up_bet_amount: NativeBalance(vec![]),
+            total_down_bet_amount: NativeBalance(vec![]),
+            start_price: Some(Decimal::from_str(start_price).unwrap()),
+            stop_price: Some(Decimal::from_str(stop_price).unwrap()),
+            outcome: None,
+            settlement_prices: prices,
+            series: None,
+            claim_window: DEFAULT_CLAIM_WINDOW,
+            claimed_amount: NativeBalance(vec![]),
+            swept_amount: None,
+            start_grace_period: DEFAULT_START_GRACE_PERIOD,
+            stop_grace_period: DEFAULT_STOP_GRACE_PERIOD,
+            pool_caps: vec![],
+            max_side_ratio_bps: None,
+        };
+        for bet in bets {
+            let stake = bet.amount.clone();
+            match bet.side {
+                Side::Up => {
+                    round.up_bets_count += 1;
+                    round.total_up_bet_amount += stake.clone();
+                }
+                Side::Down => {
+                    round.down_bets_count += 1;
+                    round.total_down_bet_amount += stake.clone();
+                }
+            }
+            round.total_bet_amount += stake;
+            round.participants_count += 1;
+        }
+        round.outcome = Some(round_outcome(&round).unwrap());
+        round
+    }
+
+    #[test]
+    fn test_winning_side() {
+        let low = Decimal::from_str("1.10").unwrap();
+        let high = Decimal::from_str("1.23").unwrap();
+        assert_eq!(winning_side(low, high), Some(Side::Up));
+        assert_eq!(winning_side(high, low), Some(Side::Down));
+        assert_eq!(winning_side(low, low), None);
+    }
+
+    #[test]
+    fn test_fee_amount_rounds_down() {
+        assert_eq!(
+            fee_amount(Uint128::from(100u128), 1_500),
+            Uint128::from(15u128)
+        );
+        assert_eq!(
+            fee_amount(Uint128::from(99u128), 1_500),
+            Uint128::from(14u128)
+        );
+        assert_eq!(
+            net_of_fee(Uint128::from(99u128), 1_500),
+            Uint128::from(84u128)
+        );
+    }
+
+    #[test]
+    fn test_winner_gets_stake_and_share_of_losing_pool() {
+        let winner = bet(Side::Up, 1000, DENOM1);
+        let loser = bet(Side::Down, 1000, DENOM1);
+        let round = round(
+            &[winner.clone(), loser.clone()],
+            "1.10",
+            "1.23",
+            vec![price(DENOM1, "1")],
+        );
+
+        let payout = compute_payout(&round, &winner).unwrap();
+        assert_eq!(
+            payout,
+            Payout {
+                kind: PayoutKind::Win,
+                coins: vec![coin(1700, DENOM1)],
+            }
+        );
+
+        let payout = compute_payout(&round, &loser).unwrap();
+        assert_eq!(payout.kind, PayoutKind::Loss);
+        assert!(payout.coins.is_empty());
+    }
+
+    #[test]
+    fn test_unchanged_price_refunds_full_stake() {
+        let up = bet(Side::Up, 1000, DENOM1);
+        let down = bet(Side::Down, 500, DENOM2);
+        let round = round(&[up.clone(), down.clone()], "1.23", "1.23", vec![]);
+
+        let payout = compute_payout(&round, &down).unwrap();
+        assert_eq!(
+            payout,
+            Payout {
+                kind: PayoutKind::Refund,
+                coins: vec![coin(500, DENOM2)],
+            }
+        );
+    }
+
+    #[test]
+    fn test_winners_share_losing_pool_by_value_across_denoms() {
+        // denom2 is worth twice as much as denom1 so both winners have the same weight
+        let winner1 = bet(Side::Down, 1000, DENOM1);
+        let winner2 = bet(Side::Down, 500, DENOM2);
+        let loser1 = bet(Side::Up, 1000, DENOM1);
+        let loser2 = bet(Side::Up, 300, DENOM2);
+        let round = round(
+            &[
+                winner1.clone(),
+                winner2.clone(),
+                loser1.clone(),
+                loser2.clone(),
+            ],
+            "1.23",
+            "1.10",
+            vec![price(DENOM1, "1.5"), price(DENOM2, "3")],
+        );
+
+        let payout1 = compute_payout(&round, &winner1).unwrap();
+        assert_eq!(payout1.coins, vec![coin(1275, DENOM1), coin(127, DENOM2)]);
+
+        let payout2 = compute_payout(&round, &winner2).unwrap();
+        assert_eq!(payout2.coins, vec![coin(425, DENOM1), coin(552, DENOM2)]);
+    }
+
+    #[test]
+    fn test_multi_denom_bet_is_paid_back_in_each_denom() {
+        let mut multi = bet(Side::Up, 500, DENOM1);
+        multi.amount += coin(500, DENOM2);
+        let single = bet(Side::Up, 1000, DENOM1);
+        let loser = bet(Side::Down, 2000, DENOM1);
+        let round = round(
+            &[multi.clone(), single, loser],
+            "1.10",
+            "1.23",
+            vec![price(DENOM1, "1"), price(DENOM2, "1")],
+        );
+
+        // half the value of the winning side, so half of the losing pool net of fees
+        let payout = compute_payout(&round, &multi).unwrap();
+        assert_eq!(payout.coins, vec![coin(1275, DENOM1), coin(425, DENOM2)]);
+
+        let mut cancelled = round;
+        cancelled.status = RoundStatus::Cancelled;
+        let refund = bet_payout(&cancelled, &multi, RoundStatus::Cancelled)
+            .unwrap()
+            .unwrap();
+        assert_eq!(refund.coins, vec![coin(500, DENOM1), coin(500, DENOM2)]);
+    }
+
+    #[test]
+    fn test_payouts_never_exceed_pool_net_of_fees() {
+        let bets = vec![
+            bet(Side::Up, 333, DENOM1),
+            bet(Side::Up, 667, DENOM1),
+            bet(Side::Up, 1, DENOM1),
+            bet(Side::Down, 1001, DENOM1),
+        ];
+        let round = round(&bets, "1", "2", vec![price(DENOM1, "0.7")]);
+
+        let mut paid = Uint128::zero();
+        for bet in bets.iter() {
+            for coin in compute_payout(&round, bet).unwrap().coins {
+                paid += coin.amount;
+            }
+        }
+        let pool = Uint128::from(2002u128);
+        let fee = fee_amount(pool, DEFAULT_FEE_BPS);
+        assert!(paid + fee <= pool);
+        // only rounding dust is left behind, at most two units per bet
+        assert!(pool - paid - fee <= Uint128::from(2 * bets.len() as u128));
+    }
+
+    #[test]
+    fn test_one_sided_round_is_a_push() {
+        let up1 = bet(Side::Up, 1000, DENOM1);
+        let up2 = bet(Side::Up, 500, DENOM1);
+        let one_sided = round(
+            &[up1.clone(), up2.clone()],
+            "1",
+            "2",
+            vec![price(DENOM1, "1")],
+        );
+        assert_eq!(one_sided.outcome, Some(RoundOutcome::Push));
+
+        let payout = compute_payout(&one_sided, &up2).unwrap();
+        assert_eq!(
+            payout,
+            Payout {
+                kind: PayoutKind::Refund,
+                coins: vec![coin(500, DENOM1)],
+            }
+        );
+
+        // a single participant can never win against nobody
+        let single = round(
+            std::slice::from_ref(&up1),
+            "1",
+            "2",
+            vec![price(DENOM1, "1")],
+        );
+        assert_eq!(
+            compute_payout(&single, &up1).unwrap().kind,
+            PayoutKind::Refund
+        );
+    }
+
+    #[test]
+    fn test_missing_settlement_price_errors() {
+        let winner = bet(Side::Up, 1000, DENOM1);
+        let loser = bet(Side::Down, 1000, DENOM1);
+        let round = round(&[winner.clone(), loser], "1", "2", vec![]);
+
+        assert!(compute_payout(&round, &winner).is_err());
+    }
+
+    #[test]
+    fn test_payout_multiplier() {
+        let multiplier = |side: u128, opposing: u128| {
+            payout_multiplier(
+                Uint256::from(side),
+                Uint256::from(opposing),
+                DEFAULT_FEE_BPS,
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            multiplier(1000, 1000),
+            Some(Decimal256::from_str("1.7").unwrap())
+        );
+        assert_eq!(
+            multiplier(1000, 3000),
+            Some(Decimal256::from_str("3.4").unwrap())
+        );
+        // a side nobody bet against is refunded
+        assert_eq!(multiplier(1000, 0), Some(Decimal256::one()));
+        assert_eq!(multiplier(0, 1000), None);
+    }
+}
@@ -0,0 +1,107 @@
+use cosmwasm_std::{Decimal, Decimal256, Deps, StdError, StdResult, Storage, Uint128};
+use cw_utils::NativeBalance;
+use kujira::fin::{BookResponse, QueryMsg as FinQueryMsg};
+use kujira::querier::KujiraQuerier;
+use kujira::query::KujiraQuery;
+
+use crate::payout::BPS_DENOMINATOR;
+use crate::state::{Config, DenomPrice, PriceSource, REPORTED_PRICES};
+
+// gets the current price of a denom from the source configured for it
+pub fn query_price(
+    deps: Deps<KujiraQuery>,
+    config: &Config,
+    denom: &str,
+    current_time: u64,
+) -> StdResult<Decimal> {
+    match config.price_source(denom) {
+        PriceSource::Oracle {} => {
+            let q = KujiraQuerier::new(&deps.querier);
+            Ok(q.query_exchange_rate(denom.to_string())?.rate)
+        }
+        PriceSource::Fin { pair } => {
+            let book: BookResponse = deps.querier.query_wasm_smart(
+                pair,
+                &FinQueryMsg::Book {
+                    limit: Some(1),
+                    offset: None,
+                },
+            )?;
+            fin_mid_price(denom, &book)
+        }
+        PriceSource::Reporter { max_age, .. } => {
+            reported_price(deps.storage, denom, max_age, current_time)
+        }
+    }
+}
+
+// gets the current price of every denom in a balance, cw20 denoms are priced under their
+// oracle denom
+pub fn bet_denom_prices(
+    deps: Deps<KujiraQuery>,
+    config: &Config,
+    balance: &NativeBalance,
+    current_time: u64,
+) -> StdResult<Vec<DenomPrice>> {
+    balance
+        .0
+        .iter()
+        .map(|coin| {
+            let price = query_price(
+                deps,
+                config,
+                &config.oracle_denom(&coin.denom),
+                current_time,
+            )?;
+            Ok(DenomPrice {
+                denom: coin.denom.clone(),
+                price,
+            })
+        })
+        .collect()
+}
+
+// returns true if the stop price moved more than max_price_deviation_bps from the start price
+pub fn exceeds_price_deviation(
+    start_price: Decimal,
+    stop_price: Decimal,
+    max_price_deviation_bps: u32,
+) -> bool {
+    let deviation = start_price.abs_diff(stop_price);
+    deviation.atomics().full_mul(BPS_DENOMINATOR)
+        > start_price.atomics().full_mul(max_price_deviation_bps)
+}
+
+// the base pools of a FIN book are the asks and the quote pools are the bids, the best of each
+// comes first
+fn fin_mid_price(denom: &str, book: &BookResponse) -> StdResult<Decimal> {
+    match (book.base.first(), book.quote.first()) {
+        (Some(ask), Some(bid)) => {
+            let mid = (ask.quote_price + bid.quote_price) * Decimal256::percent(50);
+            Ok(Decimal::new(Uint128::try_from(mid.atomics())?))
+        }
+        _ => Err(StdError::generic_err(format!(
+            "FIN book of {} has no bid or no ask",
+            denom
+        ))),
+    }
+}
+
+// the latest reported price of a denom, it is only used while it is at most max_age seconds old
+fn reported_price(
+    storage: &dyn Storage,
+    denom: &str,
+    max_age: u64,
+    current_time: u64,
+) -> StdResult<Decimal> {
+    let reported = REPORTED_PRICES
+        .may_load(storage, denom.to_string())?
+        .ok_or_else(|| StdError::not_found("Reported price"))?;
+    if current_time > reported.reported_at.saturating_add(max_age) {
+        return Err(StdError::generic_err(format!(
+            "reported price of {} is older than {} seconds",
+            denom, max_age
+        )));
+    }
+    Ok(reported.price)
+}
@@ -2,37 +2,338 @@ use cw_utils::NativeBalance;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Decimal};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex, UniqueIndex,
+};
+
+use crate::helpers::cw20_address;
+use crate::ContractError;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     // contract admins allowed to call private functions
     pub admins: Vec<Addr>,
-    // denom of the asset users will be betting on
-    pub asset_denom: String,
-    // denoms that users are allowed to bet with
-    pub accepted_bet_denoms: Vec<String>,
+    // denoms of the assets rounds can be created for
+    pub asset_denoms: Vec<String>,
+    // denoms that users are allowed to bet with and the bet limits for each
+    pub accepted_bet_denoms: Vec<BetDenom>,
+    // fee in basis points taken from the pool of new rounds
+    pub fee_bps: u16,
+    // bounds in seconds for the duration of a round
+    pub min_round_duration: u64,
+    pub max_round_duration: u64,
+    // minimum seconds between creating a round and its start time, this is the betting window
+    pub min_lead_time: u64,
+    // seconds after start_time and stop_time within which a round has to be started and stopped,
+    // a round that misses either window can be cancelled and its bets refunded. each round keeps
+    // the grace periods it was created with
+    pub start_grace_period: u64,
+    pub stop_grace_period: u64,
+    // share of a round's fee in basis points paid to each caller that starts or stops the round
+    pub keeper_reward_bps: u16,
+    // seconds after a round is settled or cancelled during which its bets can be claimed,
+    // whatever is left unclaimed afterwards can be swept to the treasury
+    pub claim_window: u64,
+    // share of a bet in basis points taken by the treasury when it is switched to the other side
+    pub side_switch_penalty_bps: u16,
+    // cw20 tokens that users are allowed to bet with
+    pub accepted_cw20_tokens: Vec<Cw20Token>,
+    // most that can be bet in each denom in a round, denoms not listed have no cap
+    pub pool_caps: Vec<Coin>,
+    // most a side can be worth compared to the other side in basis points, no limit when not set
+    pub max_side_ratio_bps: Option<u32>,
+    // most the stop price can move from the start price in basis points, a bigger move is
+    // treated as a bad oracle price. no limit when not set
+    pub max_price_deviation_bps: Option<u32>,
+    // seconds after the start or stop time during which a bad oracle price only fails the call,
+    // afterwards the round is cancelled instead. rounds are never cancelled this way when not set
+    pub oracle_grace_period: Option<u64>,
+    // where the prices of denoms are read from, denoms not listed are priced by the oracle
+    pub price_sources: Vec<DenomPriceSource>,
+}
+
+// a native denom accepted for bets, the limits apply to the total a user bets in the denom
+// in a round
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BetDenom {
+    pub denom: String,
+    pub min_bet: Uint128,
+    // no maximum when not set
+    pub max_bet: Option<Uint128>,
+}
+
+impl BetDenom {
+    /// accepts the denom without any bet limits
+    pub fn unlimited(denom: impl Into<String>) -> Self {
+        BetDenom {
+            denom: denom.into(),
+            min_bet: Uint128::zero(),
+            max_bet: None,
+        }
+    }
+}
+
+// a cw20 token accepted for bets, it is tracked in balances under its cw20 denom
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw20Token {
+    pub address: Addr,
+    // denom the oracle prices the token under
+    pub oracle_denom: String,
+    // limits on the total a user bets in the token in a round, no maximum when not set
+    pub min_bet: Uint128,
+    pub max_bet: Option<Uint128>,
+}
+
+// where the contract reads the price of a denom from
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    // the kujira oracle module
+    Oracle {},
+    // mid price of the best bid and ask of a FIN pair whose base is the denom, the pair has to be
+    // quoted in the same unit as the oracle prices
+    Fin { pair: Addr },
+    // the latest price posted by a trusted reporter, it can't be used once it is older than
+    // max_age seconds
+    Reporter { reporter: Addr, max_age: u64 },
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DenomPriceSource {
+    pub denom: String,
+    pub source: PriceSource,
+}
+
+// 30 days
+pub const DEFAULT_CLAIM_WINDOW: u64 = 2_592_000;
+
+// 5 mins
+pub const DEFAULT_START_GRACE_PERIOD: u64 = 300;
+pub const DEFAULT_STOP_GRACE_PERIOD: u64 = 300;
+
 impl Config {
     /// returns true if the address is a registered admin
     pub fn is_admin(&self, addr: impl AsRef<str>) -> bool {
         let addr = addr.as_ref();
         self.admins.iter().any(|a| a.as_ref() == addr)
     }
+
+    /// returns true if users can bet with the denom, native or cw20
+    pub fn accepts_bet_denom(&self, denom: &str) -> bool {
+        self.bet_denom(denom).is_some() || self.cw20_token(denom).is_some()
+    }
+
+    /// returns the accepted native bet denom and its limits
+    pub fn bet_denom(&self, denom: &str) -> Option<&BetDenom> {
+        self.accepted_bet_denoms.iter().find(|d| d.denom == denom)
+    }
+
+    /// returns the minimum and maximum bet of an accepted native or cw20 denom
+    pub fn bet_limits(&self, denom: &str) -> Option<(Uint128, Option<Uint128>)> {
+        match self.bet_denom(denom) {
+            Some(bet_denom) => Some((bet_denom.min_bet, bet_denom.max_bet)),
+            None => self
+                .cw20_token(denom)
+                .map(|token| (token.min_bet, token.max_bet)),
+        }
+    }
+
+    /// returns the accepted cw20 token tracked under the denom
+    pub fn cw20_token(&self, denom: &str) -> Option<&Cw20Token> {
+        let address = cw20_address(denom)?;
+        self.accepted_cw20_tokens
+            .iter()
+            .find(|token| token.address.as_str() == address)
+    }
+
+    /// returns where the price of a denom is read from
+    pub fn price_source(&self, denom: &str) -> PriceSource {
+        self.price_sources
+            .iter()
+            .find(|source| source.denom == denom)
+            .map_or(PriceSource::Oracle {}, |source| source.source.clone())
+    }
+
+    /// returns the denom the oracle prices a bet denom under
+    pub fn oracle_denom(&self, denom: &str) -> String {
+        match self.cw20_token(denom) {
+            Some(token) => token.oracle_denom.clone(),
+            None => denom.to_string(),
+        }
+    }
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
+// config stored before multiple assets were supported, only read when migrating
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub admins: Vec<Addr>,
+    pub asset_denom: String,
+    pub accepted_bet_denoms: Vec<String>,
+}
+
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum Side {
     Up,
     Down,
 }
 
+// lifecycle of a round
+// Scheduled: created but not accepting bets until its open time
+// Open: accepting bets, bets can be withdrawn until the start time
+// Locked: started, the start price is recorded and no more bets are accepted
+// Settled: stopped, the stop price is recorded and wins can be claimed
+// Cancelled: missed its start or stop window, every bet can be refunded
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum RoundStatus {
+    Scheduled,
+    Open,
+    Locked,
+    Settled,
+    Cancelled,
+}
+
+impl RoundStatus {
+    /// returns true if a round in this status is allowed to move to the next one
+    pub fn can_transition_to(&self, next: RoundStatus) -> bool {
+        use RoundStatus::*;
+        matches!(
+            (self, next),
+            (Scheduled, Open) | (Open, Locked) | (Locked, Settled) | (Open | Locked, Cancelled)
+        )
+    }
+}
+
+// result of a settled round, a push means every bet is refunded without a fee
+// because the price did not move or nobody bet against the other side
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum RoundOutcome {
+    Up,
+    Down,
+    Push,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Round {
+    pub id: u64,
+    pub name: String,
+    pub created_at: u64,
+    pub creator: Addr,
+    // denom of the asset whose price users are betting on in this round
+    pub asset_denom: String,
+    // fee rate in basis points at the time the round was created
+    pub fee_bps: u16,
+    pub status: RoundStatus,
+    // time of the last status change
+    pub status_updated_at: u64,
+    // times the round was locked and settled, set by the status transitions
+    pub started_at: Option<u64>,
+    pub stopped_at: Option<u64>,
+    pub started_by: Option<Addr>,
+    pub stopped_by: Option<Addr>,
+    // time the round starts accepting bets
+    pub open_time: u64,
+    pub start_time: u64,
+    pub stop_time: u64,
+    pub participants_count: u128,
+    pub up_bets_count: u128,
+    pub down_bets_count: u128,
+    pub total_bet_amount: NativeBalance,
+    pub total_up_bet_amount: NativeBalance,
+    pub total_down_bet_amount: NativeBalance,
+    pub start_price: Option<Decimal>,
+    pub stop_price: Option<Decimal>,
+    // set when the round is settled
+    pub outcome: Option<RoundOutcome>,
+    // prices of the bet denoms when the round was stopped, used to split the pool
+    pub settlement_prices: Vec<DenomPrice>,
+    // name of the series the round was created for, if any
+    pub series: Option<String>,
+    // claim window at the time the round was created
+    pub claim_window: u64,
+    // total paid out to the bettors of the round so far
+    pub claimed_amount: NativeBalance,
+    // set when the unclaimed amount was swept to the treasury after the claim window
+    pub swept_amount: Option<NativeBalance>,
+    // grace periods at the time the round was created
+    pub start_grace_period: u64,
+    pub stop_grace_period: u64,
+    // pool caps and side ratio limit at the time the round was created
+    pub pool_caps: Vec<Coin>,
+    pub max_side_ratio_bps: Option<u32>,
+}
+
+impl Round {
+    /// moves the round to the next status, this is the only place a round status is changed
+    pub fn transition(&mut self, next: RoundStatus, at: u64) -> Result<(), ContractError> {
+        if !self.status.can_transition_to(next) {
+            return Err(ContractError::InvalidRoundTransition {
+                from: self.status,
+                to: next,
+            });
+        }
+        self.status = next;
+        self.status_updated_at = at;
+        match next {
+            RoundStatus::Locked => self.started_at = Some(at),
+            RoundStatus::Settled => self.stopped_at = Some(at),
+            RoundStatus::Scheduled | RoundStatus::Open | RoundStatus::Cancelled => {}
+        }
+        Ok(())
+    }
+
+    /// returns the pool cap of a denom in this round
+    pub fn pool_cap(&self, denom: &str) -> Option<Uint128> {
+        self.pool_caps
+            .iter()
+            .find(|cap| cap.denom == denom)
+            .map(|cap| cap.amount)
+    }
+
+    /// returns true once the claim window of a settled or cancelled round has passed
+    pub fn claim_window_expired(&self, current_time: u64) -> bool {
+        matches!(self.status, RoundStatus::Settled | RoundStatus::Cancelled)
+            && current_time > self.status_updated_at.saturating_add(self.claim_window)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DenomPrice {
+    pub denom: String,
+    pub price: Decimal,
+}
+
+pub struct RoundIndexes<'a> {
+    // round names are unique so rounds can still be looked up by name
+    pub name: UniqueIndex<'a, String, Round, u64>,
+}
+
+impl<'a> IndexList<Round> for RoundIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Round>> + '_> {
+        let v: Vec<&dyn Index<Round>> = vec![&self.name];
+        Box::new(v.into_iter())
+    }
+}
+
+// u64 here is the id of the round, ids are assigned in creation order starting from 1
+pub fn rounds<'a>() -> IndexedMap<'a, u64, Round, RoundIndexes<'a>> {
+    let indexes = RoundIndexes {
+        name: UniqueIndex::new(|round| round.name.clone(), "rounds__name"),
+    };
+    IndexedMap::new("rounds", indexes)
+}
+
+// id of the latest round
+pub const ROUND_COUNT: Item<u64> = Item::new("round_count");
+
+// rounds stored before ids, statuses and per-round assets and fees were introduced, only read
+// when migrating
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyRound {
     pub created_at: u64,
     pub creator: Addr,
     pub is_started: bool,
@@ -52,11 +353,52 @@ pub struct Round {
 }
 
 // string here is the name of the round
-pub const ROUND: Map<String, Round> = Map::new("round");
+pub const LEGACY_ROUND: Map<String, LegacyRound> = Map::new("round");
+
+// recurring rounds for an asset, stopping or cancelling the latest round of an active series
+// opens the next one so there is always a round to bet on
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundSeries {
+    pub creator: Addr,
+    pub asset_denom: String,
+    // seconds between the start times of consecutive rounds
+    pub interval: u64,
+    // duration of each round in seconds
+    pub duration: u64,
+    // denoms that users are allowed to bet with in rounds of this series
+    pub bet_denoms: Vec<String>,
+    // sequence number of the latest round, rounds are named "<series name>-<sequence>"
+    pub latest_sequence: u64,
+    pub latest_round: String,
+    // an inactive series stops opening new rounds
+    pub active: bool,
+}
+
+impl RoundSeries {
+    /// returns the name of the round with the given sequence number in this series
+    pub fn round_name(series_name: &str, sequence: u64) -> String {
+        format!("{}-{}", series_name, sequence)
+    }
+}
+
+// string here is the name of the series
+pub const ROUND_SERIES: Map<String, RoundSeries> = Map::new("round_series");
 
 // this stores a user's bet amount and side in a given round
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Bet {
+    pub side: Side,
+    // stake in every denom the bet was placed with
+    pub amount: NativeBalance,
+    pub win_claimed: bool,
+    pub placed_at: u64,
+    // set once the bet is found to have lost so claiming across rounds skips it
+    pub lost: bool,
+}
+
+// bets stored before a bet could hold several denoms, only read when migrating
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SingleDenomBet {
     pub side: Side,
     pub amount: u128,
     pub denom: String,
@@ -64,9 +406,62 @@ pub struct Bet {
     pub placed_at: u64,
 }
 
-// string here is the name of the round the user is betting on
+impl From<SingleDenomBet> for Bet {
+    fn from(bet: SingleDenomBet) -> Self {
+        Bet {
+            side: bet.side,
+            amount: NativeBalance(vec![Coin {
+                denom: bet.denom,
+                amount: Uint128::from(bet.amount),
+            }]),
+            win_claimed: bet.win_claimed,
+            placed_at: bet.placed_at,
+            lost: false,
+        }
+    }
+}
+
+pub struct BetIndexes<'a> {
+    // bets of a user across rounds, ordered by round id
+    pub user: MultiIndex<'a, Addr, Bet, (u64, Addr)>,
+}
+
+impl<'a> IndexList<Bet> for BetIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Bet>> + '_> {
+        let v: Vec<&dyn Index<Bet>> = vec![&self.user];
+        Box::new(v.into_iter())
+    }
+}
+
+// the user is read from the bet key, the key is always written by the contract so it can
+// always be decoded
+fn bet_user(pk: &[u8], _bet: &Bet) -> Addr {
+    let (_, user) = <(u64, Addr)>::from_slice(pk).expect("bet keys are (round id, user)");
+    user
+}
+
+// u64 here is the id of the round the user is betting on
 // Addr is the address of the user who is betting
-pub const BET: Map<(String, Addr), Bet> = Map::new("bet");
+pub fn bets<'a>() -> IndexedMap<'a, (u64, Addr), Bet, BetIndexes<'a>> {
+    let indexes = BetIndexes {
+        user: MultiIndex::new(bet_user, "round_bet", "round_bet__user"),
+    };
+    IndexedMap::new("round_bet", indexes)
+}
+
+// progress of paying out a round's bets directly to the bettors
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Distribution {
+    // bets are paid in the order of the bettor address, this is the last one paid
+    pub last_user: Option<Addr>,
+    pub finished: bool,
+}
+
+// u64 here is the id of the round being distributed
+pub const DISTRIBUTION: Map<u64, Distribution> = Map::new("distribution");
+
+// bets keyed by round name before ids were introduced, only read when migrating
+pub const LEGACY_BET: Map<(String, Addr), SingleDenomBet> = Map::new("bet");
 
 // this is stores the total amount that has been collected in fees
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -75,3 +470,13 @@ pub struct TreasuryBalance {
 }
 
 pub const TREASURYBALANCE: Item<TreasuryBalance> = Item::new("treasurybalance");
+
+// latest price posted for a denom priced by a trusted reporter
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReportedPrice {
+    pub price: Decimal,
+    pub reported_at: u64,
+}
+
+// string here is the denom the price was reported for
+pub const REPORTED_PRICES: Map<String, ReportedPrice> = Map::new("reported_prices");